@@ -0,0 +1,42 @@
+/** Benchmark of the sparse weights against the dense ones (`cargo bench --bench sparse`) */
+use std::time::Instant;
+use rand::Rng;
+use spiking_neural_network::campaign::rng_from_seed;
+use spiking_neural_network::failure::{Conf, Failure};
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::snn::builder::SnnBuilder;
+use spiking_neural_network::sparse::SparseFormat;
+
+const N_INPUTS: usize = 784;
+const N_NEURONS: usize = 400;
+const DURATION: usize = 100;
+const REPETITIONS: u32 = 5;
+
+fn main() {
+    let mut rng = rng_from_seed(0, 0);
+    let input: Vec<Vec<u8>> = (0..N_INPUTS).map(|_| (0..DURATION).map(|_| rng.gen_bool(0.2) as u8).collect()).collect();
+
+    println!("density,storage,ms_per_sample");
+    for density in [1.0, 0.1, 0.01] {
+        let weights: Vec<Vec<f64>> = (0..N_NEURONS)
+            .map(|_| (0..N_INPUTS).map(|_| if rng.gen_bool(density) { rng.gen_range(0.0..0.1) } else { 0.0 }).collect())
+            .collect();
+        let builder = SnnBuilder::<LifNeuron, Conf>::new(N_INPUTS)
+            .add_layer_with_same_neurons(LifNeuron::new(1.0, 0.0, 0.0, 1.0, 1.0), N_NEURONS, weights,
+                                         vec![vec![0.0; N_NEURONS]; N_NEURONS], Conf::new(vec![], Failure::None, 0));
+
+        for (name, format) in [("dense", None), ("csr", Some(SparseFormat::Csr)), ("csc", Some(SparseFormat::Csc))] {
+            let mut snn = match format {
+                Some(format) => builder.clone().sparse_weights(format).build(),
+                None => builder.clone().build(),
+            };
+
+            let start = Instant::now();
+            for _ in 0..REPETITIONS {
+                snn.process(&input);
+            }
+            let elapsed = start.elapsed() / REPETITIONS;
+            println!("{},{},{:.3}", density, name, elapsed.as_secs_f64() * 1000.0);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/**
+Command line arguments: a command followed by options given as `--flag value`.
+ */
+pub struct Args {
+    command: String,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    /**
+    It parses the arguments (without the name of the program). No command means `help`.
+    - If a flag has no value or is repeated, an error is returned
+     */
+    pub fn parse(args: impl Iterator<Item=String>) -> Result<Self, String> {
+        let mut args = args.peekable();
+        let command = match args.peek() {
+            Some(arg) if !arg.starts_with("--") => args.next().unwrap(),
+            _ => "help".to_string(),
+        };
+
+        let mut options = HashMap::new();
+        while let Some(flag) = args.next() {
+            if !flag.starts_with("--") {
+                return Err(format!("Unexpected argument {}", flag));
+            }
+            let value = args.next().ok_or_else(|| format!("Missing value of {}", flag))?;
+            if options.insert(flag.clone(), value).is_some() {
+                return Err(format!("Repeated option {}", flag));
+            }
+        }
+
+        Ok(Self { command, options })
+    }
+
+    pub fn get_command(&self) -> &str { &self.command }
+
+    pub fn get(&self, flag: &str) -> Option<&str> {
+        self.options.get(flag).map(|value| value.as_str())
+    }
+
+    pub fn get_or(&self, flag: &str, default: &str) -> String {
+        self.get(flag).unwrap_or(default).to_string()
+    }
+
+    /** value of the flag converted to T, the default if the flag is not given */
+    pub fn get_parsed<T: FromStr>(&self, flag: &str, default: T) -> Result<T, String> {
+        match self.get(flag) {
+            Some(value) => value.parse().map_err(|_| format!("Invalid value of {}: {}", flag, value)),
+            None => Ok(default),
+        }
+    }
+
+    /** it checks that only the given flags are used by the command */
+    pub fn check_flags(&self, allowed: &[&str]) -> Result<(), String> {
+        match self.options.keys().find(|flag| !allowed.contains(&flag.as_str())) {
+            Some(flag) => Err(format!("Unknown option {} for the command {}", flag, self.command)),
+            None => Ok(()),
+        }
+    }
+}
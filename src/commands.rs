@@ -0,0 +1,379 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+use spiking_neural_network::campaign::Campaign;
+use spiking_neural_network::checkpoint::Checkpoint;
+use spiking_neural_network::evaluation::classify;
+use spiking_neural_network::failure::*;
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::network::SNN;
+use spiking_neural_network::results::{FaultResult, read_results, ResultFormat, ResultStore, SampleOutcome};
+use spiking_neural_network::snn::builder::SnnBuilder;
+use spiking_neural_network::spike_file::{convert_spike_file, SpikeEncoding};
+use spiking_neural_network::spike_reader::SpikeReader;
+use spiking_neural_network::spike_vec::SpikeVec;
+use crate::cli::Args;
+use crate::demo;
+use crate::{build_intra_weights, build_neurons, get_fault_name, get_file_name, load_input_spikes, read_assignments,
+            read_extra_weights, read_labels, DEFAULT_DIR, N_BITS, N_INPUTS, N_INSTANTS, N_NEURONS, SAMPLES, SEED};
+
+/**
+Command `run`: it processes the input samples with the fault free network and prints the accuracy.
+The samples are read from the input as they are processed, `--threads` at a time.
+ */
+pub fn run(args: &Args) -> Result<(), String> {
+    args.check_flags(&["--dir", "--input", "--samples", "--threads", "--output"])?;
+    let dir = args.get_or("--dir", DEFAULT_DIR);
+    let samples = args.get_parsed("--samples", SAMPLES)?;
+    let threads = get_threads(args)?;
+
+    let input = args.get_or("--input", &format!("{dir}/inputSpikes.zip"));
+    let mut reader = SpikeReader::open(&input, N_INPUTS, N_INSTANTS)
+        .map_err(|e| format!("Something went wrong opening {}: {}", input, e))?
+        .take(samples);
+    let labels = read_labels(&dir, samples);
+    let assignments = read_assignments(&dir);
+    let snn = build_network(&dir, Conf::new(vec![], Failure::None, 0));
+
+    /* spike count of each output neuron, one per line (as outputCounters.txt) */
+    let mut output_file = match args.get("--output") {
+        Some(path_output) => Some(BufWriter::new(File::create(path_output)
+            .map_err(|e| format!("Something went wrong opening {}: {}", path_output, e))?)),
+        None => None,
+    };
+
+    let start = Instant::now();
+    let (mut processed, mut correct) = (0, 0);
+    loop {
+        let input_spikes = reader.by_ref().take(threads).collect::<std::io::Result<Vec<_>>>()
+            .map_err(|e| format!("Something went wrong reading {}: {}", input, e))?;
+        if input_spikes.is_empty() {
+            break;
+        }
+
+        for output_spikes in snn.process_batch_parallel(&input_spikes, threads) {
+            if classify(&output_spikes, &assignments) == Some(labels[processed]) {
+                correct += 1;
+            }
+            processed += 1;
+
+            if let Some(output_file) = output_file.as_mut() {
+                for neuron_spikes in output_spikes.iter() {
+                    writeln!(output_file, "{}", neuron_spikes.iter().map(|spike| *spike as u32).sum::<u32>())
+                        .map_err(|e| format!("Something went wrong writing the output: {}", e))?;
+                }
+            }
+        }
+    }
+
+    println!("Processed {} samples in {:.2} s", processed, start.elapsed().as_secs_f64());
+    println!("Accuracy: {:.2}%", correct as f64 / processed.max(1) as f64 * 100.0);
+    Ok(())
+}
+
+/**
+Command `campaign`: it injects the faults of the campaign, simulating each one on all the samples.
+The faults are shared among the worker threads; each result is appended to the results file and the fault
+is marked in the checkpoint as soon as its simulation ends, so an interrupted campaign can be resumed.
+ */
+pub fn campaign(args: &Args) -> Result<(), String> {
+    args.check_flags(&["--dir", "--input", "--samples", "--threads", "--spec", "--seed", "--results", "--checkpoint"])?;
+    let dir = args.get_or("--dir", DEFAULT_DIR);
+    let samples = args.get_parsed("--samples", SAMPLES)?;
+    let threads = get_threads(args)?;
+
+    /* the campaign of the specification file, otherwise a fault site for each component */
+    let campaign = match args.get("--spec") {
+        Some(path_spec) => {
+            let spec = fs::read_to_string(path_spec).map_err(|e| format!("Something went wrong reading {}: {}", path_spec, e))?;
+            Campaign::from_spec(&spec).map_err(|e| format!("{}: {}", path_spec, e))?
+        }
+        None => Campaign::new(SEED, vec![
+            Components::Ts, Components::Dt, Components::Weights,
+            Components::IntraWeights, Components::PrevSpikes,
+            Components::VTh, Components::VMem, Components::VReset,
+            Components::VRest, Components::Tau], N_NEURONS, N_BITS),
+    };
+    let campaign = match args.get("--seed") {
+        Some(_) => Campaign::new(args.get_parsed("--seed", SEED)?, campaign.get_components(), campaign.get_num_neurons(), campaign.get_num_bits()),
+        None => campaign,
+    };
+    if campaign.get_num_neurons() > N_NEURONS {
+        return Err(format!("The campaign targets {} neurons, but the layer has {}", campaign.get_num_neurons(), N_NEURONS));
+    }
+    println!("{}", campaign.get_spec());
+
+    let start = Instant::now();
+
+    /* results file of the campaign and checkpoint of the faults completed */
+    let path_results = args.get_or("--results", &format!("{dir}/logs/results.csv"));
+    let path_checkpoint = args.get_or("--checkpoint", &format!("{dir}/logs/checkpoint.txt"));
+    for path in [&path_results, &path_checkpoint] {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Something went wrong creating {}: {}", parent.display(), e))?;
+        }
+    }
+    /* (both refuse to resume the campaign on other samples) */
+    let input = args.get_or("--input", &format!("{dir}/inputSpikes.zip"));
+    let input = fs::canonicalize(&input).map_or(input, |path| path.display().to_string());
+    let mut store = ResultStore::open(&path_results, ResultFormat::from_path(&path_results), &campaign.get_run_spec(&input, samples))
+        .map_err(|e| format!("Something went wrong opening the results (use another --results file for a new campaign): {}", e))?;
+    let mut checkpoint = Checkpoint::open(&path_checkpoint, &campaign, &input, samples)
+        .map_err(|e| format!("Something went wrong opening the checkpoint (delete {} to restart the campaign): {}", path_checkpoint, e))?;
+    if checkpoint.get_num_done() > 0 {
+        println!("Resuming campaign: {} faults already completed", checkpoint.get_num_done());
+    }
+
+    /* file collecting the faults skipped because masked (appended, to keep the ones of an interrupted run) */
+    let path_masked = format!("{dir}/logs/masked.txt");
+    let mut masked_file = OpenOptions::new().append(true).create(true).open(&path_masked)
+        .map_err(|e| format!("Something went wrong opening {}: {}", path_masked, e))?;
+
+    /* build parameters of the network */
+    let neurons: Vec<LifNeuron> = build_neurons(&dir);
+    let extra_weights: Vec<Vec<f64>> = read_extra_weights(&dir);
+    let intra_weights: Vec<Vec<f64>> = build_intra_weights();
+    let input_spikes = load_inputs(args, &dir, samples)?;
+    let labels = read_labels(&dir, samples);
+    let assignments = read_assignments(&dir);
+    let build = |configuration: Conf| SnnBuilder::new(N_INPUTS)
+        .add_layer(neurons.clone(), extra_weights.clone(), intra_weights.clone(), configuration)
+        .build();
+
+    /* golden outputs, to classify the effect of each fault on each sample (packed, as the inputs) */
+    let golden_outputs: Vec<Vec<SpikeVec>> = thread::scope(|scope| {
+        let handles: Vec<_> = input_spikes.chunks(samples.div_ceil(threads).max(1))
+            .map(|chunk| {
+                let mut snn = build(Conf::new(vec![], Failure::None, 0));
+                scope.spawn(move || chunk.iter().map(|sample_spikes| snn.process_packed(sample_spikes)).collect::<Vec<_>>())
+            })
+            .collect();
+        handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+    });
+    let golden_correct = golden_outputs.iter().zip(labels.iter())
+        .filter(|(output_spikes, label)| classify(&unpack(output_spikes), &assignments) == Some(**label))
+        .count();
+    let golden_accuracy = golden_correct as f64 / samples.max(1) as f64 * 100.0;
+
+    /* the fault is injected in the first instant processed by the layer, the first one with an input spike */
+    let injection_times: Vec<Option<u64>> = input_spikes.iter().map(|spikes| first_input_instant(spikes)).collect();
+
+    /* faults to simulate: the ones completed by an interrupted run are skipped, the provably masked ones are recorded without simulation */
+    let confs = campaign.generate_confs();
+    let num_faults = confs.len();
+    let mut faults = Vec::<(usize, Conf, FaultResult)>::new();
+    for (fault_id, configuration) in confs.into_iter().enumerate() {
+        /* (a fault whose result was written just before a crash may be missing from the checkpoint) */
+        if checkpoint.is_done(fault_id) || store.contains(0, &configuration) {
+            continue;
+        }
+
+        let probe = build(configuration.clone());
+        let mut result = FaultResult::new(0, &configuration);
+        let weight_position = probe.get_layers()[0].get_fault_weight_position();
+        result.row = weight_position.map(|(row, _)| row);
+        result.col = weight_position.map(|(_, col)| col);
+        result.injection_times = injection_times.clone();
+
+        if !probe.is_fault_effective(N_INSTANTS as u64) {
+            println!("Useless simulation {}", get_fault_name(&configuration));
+            masked_file.write_all(format!("{}\n", get_file_name(&configuration)).as_bytes())
+                .map_err(|e| format!("Something went wrong writing into {}: {}", path_masked, e))?;
+
+            /* (a masked fault leaves every output as the golden one) */
+            result.outcomes = vec![SampleOutcome::Masked; input_spikes.len()];
+            result.accuracy = Some(golden_accuracy);
+            store.append(&result).map_err(|e| format!("Something went wrong writing into {}: {}", path_results, e))?;
+            checkpoint.mark_done(fault_id).map_err(|e| format!("Something went wrong writing into the checkpoint: {}", e))?;
+            continue;
+        }
+        faults.push((fault_id, configuration, result));
+    }
+    println!("Simulating {} faults of {} with {} threads", faults.len(), num_faults, threads);
+
+    let faults = Mutex::new(faults.into_iter());
+    let store = Mutex::new(store);
+    let checkpoint = Mutex::new(checkpoint);
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let fault = faults.lock().unwrap().next();
+                let Some((fault_id, configuration, mut result)) = fault else { break };
+                let start_fault = Instant::now();
+                let mut correct = 0;
+
+                /* run simulation over snn with fault configuration */
+                for (i, sample_spikes) in input_spikes.iter().enumerate() {
+                    let output_spikes = unpack(&build(configuration.clone()).process_packed(sample_spikes));
+                    result.outcomes.push(SampleOutcome::from_outputs(&unpack(&golden_outputs[i]), &output_spikes, Some(&assignments)));
+                    if classify(&output_spikes, &assignments) == Some(labels[i]) {
+                        correct += 1;
+                    }
+                }
+
+                result.accuracy = Some(correct as f64 / samples.max(1) as f64 * 100.0);
+                result.runtime = start_fault.elapsed();
+                println!("Fault {}/{} {} - changed {}/{} samples - accuracy {:.2}%", fault_id + 1, num_faults,
+                         get_fault_name(&configuration), result.get_num_effective(), samples, result.accuracy.unwrap());
+
+                store.lock().unwrap().append(&result).expect("Something went wrong writing into the results file!");
+                checkpoint.lock().unwrap().mark_done(fault_id).expect("Something went wrong writing into the checkpoint!");
+            });
+        }
+    });
+
+    /* print duration */
+    let min = start.elapsed().as_secs() as f64 / 60.0;
+    println!("Duration simulations: {:.2} min", min);
+    println!("Results written into {}", path_results);
+    Ok(())
+}
+
+/**
+Command `demo`: one run with a transient fault and one fault free run on the first sample.
+ */
+pub fn demo(args: &Args) -> Result<(), String> {
+    args.check_flags(&["--dir", "--input"])?;
+    let dir = args.get_or("--dir", DEFAULT_DIR);
+
+    let input_spikes = load_inputs(args, &dir, 1)?;
+    fs::create_dir_all(format!("{dir}/configurations")).map_err(|e| format!("Something went wrong creating {}/configurations: {}", dir, e))?;
+    demo::demo(&dir, input_spikes.first().map(|sample_spikes| unpack(sample_spikes)).unwrap_or_default());
+    Ok(())
+}
+
+/**
+Command `inspect`: it prints the shape of the network and the ranges of its parameters.
+ */
+pub fn inspect(args: &Args) -> Result<(), String> {
+    args.check_flags(&["--dir"])?;
+    let dir = args.get_or("--dir", DEFAULT_DIR);
+    let snn = build_network(&dir, Conf::new(vec![], Failure::None, 0));
+
+    println!("Network: {} inputs, {} layers, {} outputs",
+             snn.get_input_layer_one_dim(), snn.get_number_layers(), snn.get_output_last_layer_dim());
+    for (i, layer) in snn.get_layers().iter().enumerate() {
+        let neurons = layer.get_neurons();
+        let weights = layer.get_weights();
+        let intra_weights = layer.get_intra_weights();
+
+        println!("Layer {}: {} neurons", i, neurons.len());
+        print_stats("thresholds", neurons.len(), 1, neurons.iter().map(|neuron| neuron.get_v_th()));
+        print_stats("rest potentials", neurons.len(), 1, neurons.iter().map(|neuron| neuron.get_v_rest()));
+        print_stats("reset potentials", neurons.len(), 1, neurons.iter().map(|neuron| neuron.get_v_reset()));
+        print_stats("tau", neurons.len(), 1, neurons.iter().map(|neuron| neuron.get_tau()));
+        print_stats("dt", neurons.len(), 1, neurons.iter().map(|neuron| neuron.get_dt()));
+        print_stats("weights", weights.len(), layer.get_input_dimension(), weights.iter().flatten().copied());
+        print_stats("intra weights", intra_weights.len(), intra_weights.len(), intra_weights.iter().flatten().copied());
+    }
+    Ok(())
+}
+
+/**
+Command `report`: it summarizes the results of a campaign for each component and fault model.
+ */
+pub fn report(args: &Args) -> Result<(), String> {
+    args.check_flags(&["--dir", "--results"])?;
+    let dir = args.get_or("--dir", DEFAULT_DIR);
+    let path_results = args.get_or("--results", &format!("{dir}/logs/results.csv"));
+    let results = read_results(&path_results, ResultFormat::from_path(&path_results))
+        .map_err(|e| format!("Something went wrong reading {}: {}", path_results, e))?;
+
+    let mut groups = BTreeMap::<(&str, &str), Vec<&FaultResult>>::new();
+    for result in results.iter() {
+        groups.entry((result.component.get_name(), result.failure.get_name())).or_default().push(result);
+    }
+
+    println!("{:<14}{:<18}{:>8}{:>12}{:>17}{:>12}{:>12}",
+             "component", "failure", "faults", "changed %", "misclassified %", "accuracy %", "runtime s");
+    for ((component, failure), group) in groups.iter() {
+        print_group(component, failure, group);
+    }
+    print_group("total", "", &results.iter().collect::<Vec<&FaultResult>>());
+    Ok(())
+}
+
+/**
+Command `convert`: it converts an input spike file (text, zip archive or binary) into a binary spike file.
+ */
+pub fn convert(args: &Args) -> Result<(), String> {
+    args.check_flags(&["--dir", "--input", "--output", "--encoding"])?;
+    let dir = args.get_or("--dir", DEFAULT_DIR);
+    let input = args.get_or("--input", &format!("{dir}/inputSpikes.zip"));
+    let output = args.get("--output").ok_or("Missing the --output file")?;
+    let encoding = match args.get_or("--encoding", "dense").as_str() {
+        "dense" => SpikeEncoding::Dense,
+        "events" => SpikeEncoding::Events,
+        encoding => return Err(format!("Unknown encoding {}, expected dense or events", encoding)),
+    };
+
+    let samples = convert_spike_file(&input, output, N_INPUTS, N_INSTANTS, encoding)
+        .map_err(|e| format!("Something went wrong converting {}: {}", input, e))?;
+    let size = |path: &str| fs::metadata(path).map_or(0, |metadata| metadata.len());
+    println!("{} samples converted: {} ({} bytes) -> {} ({} bytes)", samples, input, size(&input), output, size(output));
+    Ok(())
+}
+
+/* a line of the report: percentages of the samples changed and misclassified, mean accuracy and total runtime */
+fn print_group(component: &str, failure: &str, results: &[&FaultResult]) {
+    let outcomes: Vec<SampleOutcome> = results.iter().flat_map(|result| result.outcomes.iter().copied()).collect();
+    let percentage = |count: usize| if outcomes.is_empty() { 0.0 } else { count as f64 / outcomes.len() as f64 * 100.0 };
+    let changed = outcomes.iter().filter(|outcome| **outcome != SampleOutcome::Masked).count();
+    let misclassified = outcomes.iter().filter(|outcome| **outcome == SampleOutcome::Misclassified).count();
+
+    let accuracies: Vec<f64> = results.iter().filter_map(|result| result.accuracy).collect();
+    let accuracy = if accuracies.is_empty() { "-".to_string() } else { format!("{:.2}", accuracies.iter().sum::<f64>() / accuracies.len() as f64) };
+    let runtime: f64 = results.iter().map(|result| result.runtime.as_secs_f64()).sum();
+
+    println!("{:<14}{:<18}{:>8}{:>12.2}{:>17.2}{:>12}{:>12.1}",
+             component, failure, results.len(), percentage(changed), percentage(misclassified), accuracy, runtime);
+}
+
+fn print_stats(name: &str, rows: usize, cols: usize, values: impl Iterator<Item=f64>) {
+    let values: Vec<f64> = values.collect();
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mean = values.iter().sum::<f64>() / values.len().max(1) as f64;
+    let non_zero = values.iter().filter(|value| **value != 0.0).count();
+
+    println!("  {:<17}{:>4}x{:<4} min {:>10.4}  mean {:>10.4}  max {:>10.4}  non zero {}",
+             name, rows, cols, min, mean, max, non_zero);
+}
+
+fn build_network(dir: &str, configuration: Conf) -> SNN<LifNeuron, Conf> {
+    SnnBuilder::new(N_INPUTS)
+        .add_layer(build_neurons(dir), read_extra_weights(dir), build_intra_weights(), configuration)
+        .build()
+}
+
+fn load_inputs(args: &Args, dir: &str, samples: usize) -> Result<Vec<Vec<SpikeVec>>, String> {
+    let input = args.get_or("--input", &format!("{dir}/inputSpikes.zip"));
+    load_input_spikes(&input, samples).map_err(|e| format!("Something went wrong reading {}: {}", input, e))
+}
+
+/* number of worker threads, by default the number of cores */
+fn get_threads(args: &Args) -> Result<usize, String> {
+    let threads = args.get_parsed("--threads", thread::available_parallelism().map_or(1, |threads| threads.get()))?;
+    if threads == 0 {
+        return Err("The number of threads must be at least 1".to_string());
+    }
+    Ok(threads)
+}
+
+/* first instant in which at least one input neuron spikes */
+fn first_input_instant(input_spikes: &[SpikeVec]) -> Option<u64> {
+    input_spikes.iter()
+        .filter_map(|neuron_spikes| neuron_spikes.iter_ones().next())
+        .min()
+        .map(|instant| instant as u64)
+}
+
+/* spikes of a sample as [neuron][instant] */
+fn unpack(spikes: &[SpikeVec]) -> Vec<Vec<u8>> {
+    spikes.iter().map(|neuron_spikes| neuron_spikes.to_vec()).collect()
+}
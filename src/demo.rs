@@ -5,17 +5,13 @@ use spiking_neural_network::failure::*;
 use spiking_neural_network::lif_neuron::LifNeuron;
 use spiking_neural_network::snn::builder::SnnBuilder;
 use std::process::{Command};
-use crate::{get_file_name, read_multiple_input_spikes,
-            build_neurons, build_intra_weights, get_current_dir,
+use crate::{get_file_name, build_neurons, build_intra_weights,
             read_extra_weights, N_NEURONS, N_INPUTS, N_INSTANTS};
 
-pub fn demo() {
-    let path = get_current_dir();
-
+pub fn demo(dir: &str, input_spikes: Vec<Vec<u8>>) {
     /* build parameters of the network */
-    let input_spikes: Vec<Vec<Vec<u8>>> = read_multiple_input_spikes(&path);
-    let neurons: Vec<LifNeuron> = build_neurons(&path);
-    let extra_weights: Vec<Vec<f64>> = read_extra_weights(&path);
+    let neurons: Vec<LifNeuron> = build_neurons(dir);
+    let extra_weights: Vec<Vec<f64>> = read_extra_weights(dir);
     let intra_weights: Vec<Vec<f64>> = build_intra_weights();
 
     /* run demo over snn with fault configuration */
@@ -23,14 +19,14 @@ pub fn demo() {
         vec![Components::VRest], Failure::TransientBitFlip(TransientBitFlip::new(0)), 10);
 
     let file_name = get_file_name(&conf_fault);
-    let path_output1 = format!("{path}/simulation/configurations/{file_name}");
+    let path_output1 = format!("{dir}/configurations/{file_name}");
     let mut output_file1 = File::create(path_output1).expect("Something went wrong opening the file outputCounters.txt!");
 
     let mut snn = SnnBuilder::new(N_INPUTS)
         .add_layer(neurons.clone(), extra_weights.clone(), intra_weights.clone(), conf_fault.clone())
         .build();
 
-    let output_spikes = snn.process(&input_spikes[0]);
+    let output_spikes = snn.process(&input_spikes);
     write_output(&mut output_file1, output_spikes);
 
 
@@ -38,19 +34,19 @@ pub fn demo() {
     let conf_no_fault = Conf::new(vec![], Failure::None, 0);
 
     let file_name = get_file_name(&conf_no_fault);
-    let path_output2 = format!("{path}/simulation/configurations/{file_name}");
+    let path_output2 = format!("{dir}/configurations/{file_name}");
     let mut output_file2 = File::create(path_output2).expect("Something went wrong opening the file outputCounters.txt!");
 
     let mut snn = SnnBuilder::new(N_INPUTS)
         .add_layer(neurons.clone(), extra_weights.clone(), intra_weights.clone(), conf_no_fault.clone())
         .build();
 
-    let output_spikes = snn.process(&input_spikes[0]);
+    let output_spikes = snn.process(&input_spikes);
     write_output(&mut output_file2, output_spikes);
 
 
     /* when all simulations are finished run the python script to print logs file */
-    let string = format!("{path}/simulation/runDemo.py");
+    let string = format!("{dir}/runDemo.py");
     let path_py = OsStr::new(&string);
     Command::new("python").arg(path_py).output()
         .expect("Error during execution of the command");
@@ -1,8 +1,29 @@
 pub use self::snn::neuron;
 pub use self::snn::lif_neuron;
+pub use self::snn::tmr_neuron;
+pub use self::snn::fixed_lif_neuron;
+pub use self::snn::fixed_point;
+pub use self::snn::float;
+pub use self::snn::evaluation;
+pub use self::snn::quantization;
 pub use self::snn::layer;
 pub use self::snn::network;
+pub use self::snn::pipeline;
+pub use self::snn::probe;
+pub use self::snn::recording;
+pub use self::snn::vcd;
+pub use self::snn::svg;
+pub use self::snn::results;
+pub use self::snn::checkpoint;
 pub use self::snn::spike_event;
+pub use self::snn::spike_vec;
+pub use self::snn::spike_reader;
+pub use self::snn::spike_file;
+pub use self::snn::aer;
 pub use self::snn::configuration;
 pub use self::snn::failure;
+pub use self::snn::campaign;
+pub use self::snn::range_guard;
+pub use self::snn::protection;
+pub use self::snn::sparse;
 pub mod snn;
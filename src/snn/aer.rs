@@ -0,0 +1,111 @@
+/** Address-Event Representation module */
+use std::io::{BufRead, Error, ErrorKind, Result, Write};
+use crate::snn::spike_event::SpikeEvent;
+use crate::snn::spike_vec::SpikeVec;
+
+/**
+Spike in Address-Event Representation: the time instant and the address (index) of the neuron which fired.
+The events are ordered by timestamp, then by address.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AerEvent {
+    pub timestamp: u64,
+    pub address: usize,
+}
+
+impl AerEvent {
+    pub fn new(timestamp: u64, address: usize) -> Self {
+        Self { timestamp, address }
+    }
+}
+
+/**
+It groups the AER events (in any order) in a SpikeEvent for each instant with at least one spike,
+in increasing order of time. Duplicated events are merged.
+- If an address is not less than `num_neurons`, the process panics
+ */
+pub(crate) fn encode_aer(num_neurons: usize, events: &[AerEvent]) -> Vec<SpikeEvent> {
+    let mut events = events.to_vec();
+    events.sort_unstable();
+
+    let mut spike_events = Vec::<SpikeEvent>::new();
+    for instant_events in events.chunk_by(|a, b| a.timestamp == b.timestamp) {
+        let mut spikes = SpikeVec::new(num_neurons);
+        for event in instant_events {
+            if event.address >= num_neurons {
+                panic!("Error: AER address {} at t={} out of the {} input neurons", event.address, event.timestamp, num_neurons);
+            }
+            spikes.set(event.address, 1);
+        }
+        spike_events.push(SpikeEvent::new(instant_events[0].timestamp, spikes));
+    }
+    spike_events
+}
+
+/** it returns the AER events of the spikes of the SpikeEvents, ordered if the SpikeEvents are */
+pub(crate) fn decode_aer(spike_events: &[SpikeEvent]) -> Vec<AerEvent> {
+    spike_events.iter()
+        .flat_map(|spike_event| spike_event.get_active().map(|address| AerEvent::new(spike_event.get_ts(), address)))
+        .collect()
+}
+
+/**
+It converts the spikes as [neuron][instant] (the input of `SNN::process`) into ordered AER events.
+ */
+pub fn spikes_to_aer(spikes: &[Vec<u8>]) -> Vec<AerEvent> {
+    let mut events: Vec<AerEvent> = spikes.iter().enumerate()
+        .flat_map(|(address, neuron_spikes)| neuron_spikes.iter().enumerate()
+            .filter(|(_, spike)| **spike != 0)
+            .map(move |(instant, _)| AerEvent::new(instant as u64, address)))
+        .collect();
+    events.sort_unstable();
+    events
+}
+
+/**
+It converts the AER events into spikes as [neuron][instant], with `num_neurons` neurons and `duration` instants.
+- If an event is out of the neurons or instants, the process panics
+ */
+pub fn aer_to_spikes(events: &[AerEvent], num_neurons: usize, duration: usize) -> Vec<Vec<u8>> {
+    let mut spikes = vec![vec![0u8; duration]; num_neurons];
+    for event in events {
+        if event.address >= num_neurons || event.timestamp as usize >= duration {
+            panic!("Error: AER event ({}, {}) out of {} neurons and {} instants", event.timestamp, event.address, num_neurons, duration);
+        }
+        spikes[event.address][event.timestamp as usize] = 1;
+    }
+    spikes
+}
+
+/**
+It reads AER events in text format, a "timestamp address" pair for each line (separated by spaces or a comma).
+Empty lines and the text after a '#' are ignored.
+- If a line is not a valid pair, an error of kind InvalidData is returned
+ */
+pub fn read_aer<R: BufRead>(reader: R) -> Result<Vec<AerEvent>> {
+    let mut events = Vec::new();
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.split('#').next().unwrap_or_default();
+        let fields: Vec<&str> = line.split(|c: char| c == ',' || c.is_whitespace()).filter(|field| !field.is_empty()).collect();
+        match fields.as_slice() {
+            [] => continue,
+            [timestamp, address] => {
+                let invalid = |_| Error::new(ErrorKind::InvalidData, format!("Invalid AER event at line {}: {}", number + 1, line.trim()));
+                events.push(AerEvent::new(timestamp.parse().map_err(invalid)?, address.parse().map_err(invalid)?));
+            }
+            _ => return Err(Error::new(ErrorKind::InvalidData, format!("Expected a timestamp and an address at line {}", number + 1))),
+        }
+    }
+    Ok(events)
+}
+
+/**
+It writes the AER events in the text format of `read_aer`, a "timestamp address" line for each event.
+ */
+pub fn write_aer<W: Write>(mut writer: W, events: &[AerEvent]) -> Result<()> {
+    for event in events {
+        writeln!(writer, "{} {}", event.timestamp, event.address)?;
+    }
+    writer.flush()
+}
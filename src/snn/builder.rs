@@ -1,20 +1,25 @@
 use std::sync::{Arc, Mutex};
 use crate::neuron::Neuron;
 use crate::snn::layer::Layer;
-use crate::snn::network::SNN;
+use crate::snn::network::{Executor, SNN};
 use crate::configuration::Configuration;
+use crate::snn::float::Float;
+use crate::snn::quantization::{quantize_weights, QuantizationScheme};
+use crate::snn::sparse::SparseFormat;
 
 /**
 Object containing the configuration parameters describing the SNN architecture
  */
 #[derive(Clone)]
 pub struct SnnParams<N: Neuron, R: Configuration> {
-    pub input_dimensions: usize,            /* dimension of the network input layer */
-    pub neurons: Vec<Vec<N>>,               /* neurons per each layer */
-    pub extra_weights: Vec<Vec<Vec<f64>>>,  /* (positive) weights between layers */
-    pub intra_weights: Vec<Vec<Vec<f64>>>,  /* (negative) weights inside the same layer */
-    pub num_layers: usize,                  /* number of layers */
-    pub configuration: Vec<R>,              /* configuration for each layer */
+    pub input_dimensions: usize,                    /* dimension of the network input layer */
+    pub neurons: Vec<Vec<N>>,                       /* neurons per each layer */
+    pub extra_weights: Vec<Vec<Vec<N::Float>>>,     /* (positive) weights between layers */
+    pub intra_weights: Vec<Vec<Vec<N::Float>>>,     /* (negative) weights inside the same layer */
+    pub num_layers: usize,                          /* number of layers */
+    pub configuration: Vec<R>,                      /* configuration for each layer */
+    pub sparse_format: Option<SparseFormat>,        /* storage of the extra weights, dense if None */
+    pub executor: Executor,                         /* way the layers are run */
 }
 
 /**
@@ -37,6 +42,8 @@ impl<N: Neuron + Clone, R: Configuration + Clone> SnnBuilder<N, R> {
                 intra_weights: vec![],
                 num_layers: 0,
                 configuration: config,
+                sparse_format: None,
+                executor: Executor::Threaded,
             }
         }
     }
@@ -51,7 +58,7 @@ impl<N: Neuron + Clone, R: Configuration + Clone> SnnBuilder<N, R> {
     - It checks that the number of neurons is equal to the number of columns of the intra weights matrix
     - It checks that the intra weights' values are all negative and in the range [-1, 0]
      */
-    fn check_intra_weights(&self, num_neurons: usize, weights: &Vec<Vec<f64>>) {
+    fn check_intra_weights(&self, num_neurons: usize, weights: &Vec<Vec<N::Float>>) {
         if num_neurons != weights.len() {
             panic!("The number of neurons must be equal to the number of rows of the intra weights matrix");
         }
@@ -60,7 +67,7 @@ impl<N: Neuron + Clone, R: Configuration + Clone> SnnBuilder<N, R> {
                 panic!("The number of neurons must be equal to the number of columns of the intra weights matrix");
             }
             for weight in row {
-                if *weight > 0.0 {
+                if *weight > N::Float::ZERO {
                     panic!("The intra weights must be negative");
                 }
             }
@@ -75,7 +82,7 @@ impl<N: Neuron + Clone, R: Configuration + Clone> SnnBuilder<N, R> {
     - It checks that the extra weights' values are all positive and in the range [0, 1]
      */
 
-    fn check_weights(&self, num_neurons: usize, weights: &Vec<Vec<f64>>) {
+    fn check_weights(&self, num_neurons: usize, weights: &Vec<Vec<N::Float>>) {
         if num_neurons != weights.len() {
             panic!("The number of neurons must be equal to the number of rows of the weights matrix");
         }
@@ -90,7 +97,7 @@ impl<N: Neuron + Clone, R: Configuration + Clone> SnnBuilder<N, R> {
             }
 
             for weight in row {
-                if *weight < 0.0 {
+                if *weight < N::Float::ZERO {
                     panic!("The weights must be positive");
                 }
             }
@@ -100,7 +107,7 @@ impl<N: Neuron + Clone, R: Configuration + Clone> SnnBuilder<N, R> {
     /**
     It adds a new layer to the network specifying all the parameters requested.
      */
-    pub fn add_layer(self, neurons: Vec<N>, extra_weights: Vec<Vec<f64>>, intra_weights: Vec<Vec<f64>>, configuration: R) -> Self {
+    pub fn add_layer(self, neurons: Vec<N>, extra_weights: Vec<Vec<N::Float>>, intra_weights: Vec<Vec<N::Float>>, configuration: R) -> Self {
         self.check_intra_weights(neurons.len(), &intra_weights);
         self.check_weights(neurons.len(), &extra_weights);
 
@@ -118,7 +125,7 @@ impl<N: Neuron + Clone, R: Configuration + Clone> SnnBuilder<N, R> {
     It adds a new layer to the network specifying all the parameters requested.
     - All neurons have the same parameters
      */
-    pub fn add_layer_with_same_neurons(self, neuron: N, num_neurons: usize, extra_weights: Vec<Vec<f64>>, intra_weights: Vec<Vec<f64>>, configuration: R) -> Self {
+    pub fn add_layer_with_same_neurons(self, neuron: N, num_neurons: usize, extra_weights: Vec<Vec<N::Float>>, intra_weights: Vec<Vec<N::Float>>, configuration: R) -> Self {
         self.check_intra_weights(num_neurons, &intra_weights);
         self.check_weights(num_neurons, &extra_weights);
 
@@ -138,6 +145,38 @@ impl<N: Neuron + Clone, R: Configuration + Clone> SnnBuilder<N, R> {
         Self { params }
     }
 
+    /**
+    It quantizes the extra weights and the intra weights of all the layers added so far on `bits` bits.
+     */
+    pub fn quantize_weights(self, bits: usize, scheme: QuantizationScheme) -> Self {
+        let mut params = self.params;
+
+        params.extra_weights = params.extra_weights.iter().map(|weights| quantize_weights(weights, bits, scheme)).collect();
+        params.intra_weights = params.intra_weights.iter().map(|weights| quantize_weights(weights, bits, scheme)).collect();
+        Self { params }
+    }
+
+    /**
+    It stores the extra weights of all the layers as sparse matrices in the given format (CSR or CSC),
+    instead of dense matrices.
+     */
+    pub fn sparse_weights(self, format: SparseFormat) -> Self {
+        let mut params = self.params;
+
+        params.sparse_format = Some(format);
+        Self { params }
+    }
+
+    /**
+    It selects how the layers of the network are run (a thread for each layer by default).
+     */
+    pub fn executor(self, executor: Executor) -> Self {
+        let mut params = self.params;
+
+        params.executor = executor;
+        Self { params }
+    }
+
     /**
     Create and initialize the whole dynamic Spiking Neural Network with the characteristics defined so far
     - If the network has no layers, the process panics
@@ -158,6 +197,8 @@ impl<N: Neuron + Clone, R: Configuration + Clone> SnnBuilder<N, R> {
         let mut extra_weights_iter = self.params.extra_weights.into_iter();
         let mut intra_weights_iter = self.params.intra_weights.into_iter();
         let mut configuration_iter = self.params.configuration.into_iter();
+        let sparse_format = self.params.sparse_format;
+        let executor = self.params.executor;
 
         /* retrieve the Neurons, the extra weights and the intra weights for each layer */
         for layer_neurons in neurons_iter {
@@ -165,10 +206,15 @@ impl<N: Neuron + Clone, R: Configuration + Clone> SnnBuilder<N, R> {
             let layer_intra_weights = intra_weights_iter.next().unwrap();
             let configuration = configuration_iter.next().unwrap().clone();
             /* create and save the new layer */
-            let new_layer = Layer::new(layer_neurons, layer_extra_weights, layer_intra_weights, configuration);
+            let mut new_layer = Layer::new(layer_neurons, layer_extra_weights, layer_intra_weights, configuration);
+            if let Some(format) = sparse_format {
+                new_layer.enable_sparse_weights(format);
+            }
             layers.push(Arc::new(Mutex::new(new_layer)));
         }
 
-        SNN::new(layers)
+        let mut snn = SNN::new(layers);
+        snn.set_executor(executor);
+        snn
     }
 }
\ No newline at end of file
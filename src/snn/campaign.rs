@@ -0,0 +1,133 @@
+/** Campaign module */
+use std::io::{Error, ErrorKind, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::failure::{Components, Conf, Failure, StuckAt0, StuckAt1, TransientBitFlip};
+
+/**
+Object describing a fault injection campaign.
+Every random choice of the campaign (bit position and neuron index of each fault site) is drawn
+from the seed carried here, so two campaigns with the same seed produce exactly the same faults.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Campaign {
+    seed: u64,                      /* seed of every random choice of the campaign */
+    components: Vec<Components>,    /* components to inject the faults into */
+    num_neurons: usize,             /* number of neurons of the faulted layer */
+    num_bits: usize,                /* fault positions are drawn in [0, num_bits) */
+}
+
+impl Campaign {
+    pub fn new(seed: u64, components: Vec<Components>, num_neurons: usize, num_bits: usize) -> Self {
+        if num_neurons == 0 || num_bits == 0 {
+            panic!("The campaign must have at least one neuron and one bit to inject faults into");
+        }
+        Self { seed, components, num_neurons, num_bits }
+    }
+
+    pub fn get_seed(&self) -> u64 { self.seed }
+    pub fn get_components(&self) -> Vec<Components> { self.components.clone() }
+    pub fn get_num_neurons(&self) -> usize { self.num_neurons }
+    pub fn get_num_bits(&self) -> usize { self.num_bits }
+
+    /**
+    It describes the campaign in one line: two campaigns with the same specification generate the same faults.
+     */
+    pub fn get_spec(&self) -> String {
+        let components: Vec<String> = self.components.iter().map(|component| component.get_name().to_string()).collect();
+        format!("campaign seed={} components={} neurons={} bits={}",
+                self.seed, components.join(","), self.num_neurons, self.num_bits)
+    }
+
+    /**
+    It describes in one line the campaign run on `num_samples` samples of the `input` file,
+    as written in the checkpoint and in the results file of the run.
+     */
+    pub fn get_run_spec(&self, input: &str, num_samples: usize) -> String {
+        format!("{} input={} samples={}", self.get_spec(), input, num_samples)
+    }
+
+    /**
+    It parses the specification of a campaign: `key=value` pairs (one per line, or on the same line as
+    the output of `get_spec`) for `seed`, `components` (names separated by commas), `neurons` and `bits`.
+    Text after a '#' is a comment and a leading `campaign` word is ignored.
+    - If a key is missing, unknown or has an invalid value, an error of kind InvalidData is returned
+     */
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let invalid = |message: String| Error::new(ErrorKind::InvalidData, message);
+
+        /* remove the comments and the spaces around '=' and ',' */
+        let text = spec.lines().map(|line| line.split('#').next().unwrap_or("")).collect::<Vec<&str>>().join(" ");
+        let text = text.split_whitespace().collect::<Vec<&str>>().join(" ")
+            .replace(" =", "=").replace("= ", "=").replace(" ,", ",").replace(", ", ",");
+
+        let (mut seed, mut components, mut num_neurons, mut num_bits) = (None, None, None, None);
+        for token in text.split_whitespace().filter(|token| *token != "campaign") {
+            let (key, value) = token.split_once('=').ok_or_else(|| invalid(format!("Expected key=value in the campaign specification: {}", token)))?;
+            let number = || value.parse::<u64>().map_err(|_| invalid(format!("Invalid value of {}: {}", key, value)));
+            match key {
+                "seed" => seed = Some(number()?),
+                "neurons" => num_neurons = Some(number()? as usize),
+                "bits" => num_bits = Some(number()? as usize),
+                "components" => components = Some(value.split(',')
+                    .map(|name| Components::from_name(name).ok_or_else(|| invalid(format!("Unknown component: {}", name))))
+                    .collect::<Result<Vec<Components>>>()?),
+                _ => return Err(invalid(format!("Unknown key of the campaign specification: {}", key))),
+            }
+        }
+
+        let missing = |key: &str| invalid(format!("Missing {} in the campaign specification", key));
+        let (num_neurons, num_bits) = (num_neurons.ok_or_else(|| missing("neurons"))?, num_bits.ok_or_else(|| missing("bits"))?);
+        if num_neurons == 0 || num_bits == 0 {
+            return Err(invalid("The campaign must have at least one neuron and one bit to inject faults into".to_string()));
+        }
+        Ok(Self::new(seed.ok_or_else(|| missing("seed"))?, components.ok_or_else(|| missing("components"))?, num_neurons, num_bits))
+    }
+
+    /**
+    It samples the fault sites of the campaign.
+    For each component a random bit and a random neuron index are drawn, and one configuration
+    per failure type (StuckAt1, StuckAt0, TransientBitFlip) is generated on that site.
+    The sampling is done sequentially before any simulation starts, so the result does not depend
+    on how many threads will run the simulations.
+     */
+    pub fn generate_confs(&self) -> Vec<Conf> {
+        let mut rng = rng_from_seed(self.seed, 0);
+        let mut confs = Vec::with_capacity(self.components.len() * 3);
+
+        for component in self.components.iter() {
+            let random_bit = rng.gen_range(0..self.num_bits);
+            let random_index = rng.gen_range(0..self.num_neurons);
+
+            let failures = vec![
+                Failure::StuckAt1(StuckAt1::new(random_bit)),
+                Failure::StuckAt0(StuckAt0::new(random_bit)),
+                Failure::TransientBitFlip(TransientBitFlip::new(random_bit)),
+            ];
+
+            for failure in failures {
+                confs.push(Conf::new(vec![component.clone()], failure, random_index));
+            }
+        }
+        confs
+    }
+}
+
+/**
+It derives the seed of an independent random stream from the campaign seed (SplitMix64).
+Any stochastic part of the simulator (fault sampling, encoders, stochastic neurons) must use its
+own stream, so that its random choices do not depend on the execution order of the others.
+ */
+pub fn derive_seed(seed: u64, stream: u64) -> u64 {
+    let mut z = seed.wrapping_add(stream.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/**
+It creates the deterministic random generator of the given stream of the campaign seed.
+ */
+pub fn rng_from_seed(seed: u64, stream: u64) -> StdRng {
+    StdRng::seed_from_u64(derive_seed(seed, stream))
+}
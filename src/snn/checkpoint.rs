@@ -0,0 +1,75 @@
+/** Checkpoint module */
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{Error, ErrorKind, Read, Result, Write};
+use std::path::Path;
+use crate::campaign::Campaign;
+
+/**
+Checkpoint of a fault injection campaign: a file starting with the specification of the campaign and of its dataset
+(input file and number of samples), followed by the id (index in `Campaign::generate_confs`) of each fault whose simulation is completed.
+Each id is flushed to disk as soon as it is marked, so after a crash the campaign restarts from the faults not completed yet.
+ */
+pub struct Checkpoint {
+    file: File,
+    spec: String,           /* specification of the campaign */
+    done: HashSet<usize>,   /* ids of the completed faults */
+}
+
+impl Checkpoint {
+    /**
+    It opens the checkpoint of the campaign run on `num_samples` samples of the `input` file, creating it if it does not exist.
+    An incomplete last line (left by an interrupted run) is removed.
+    - If the file is the checkpoint of another campaign (different seed or parameters) or dataset, an error of kind InvalidInput is returned
+    - If a line is not a valid id, an error of kind InvalidData is returned
+     */
+    pub fn open(path: impl AsRef<Path>, campaign: &Campaign, input: &str, num_samples: usize) -> Result<Self> {
+        let spec = campaign.get_run_spec(input, num_samples);
+        let mut file = OpenOptions::new().read(true).append(true).create(true).open(path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        let complete_len = contents.rfind('\n').map_or(0, |last| last + 1);
+        if complete_len < contents.len() {
+            file.set_len(complete_len as u64)?;
+        }
+
+        let mut lines = contents[..complete_len].lines();
+        let done = match lines.next() {
+            None => {
+                writeln!(file, "{}", spec)?;
+                file.sync_data()?;
+                HashSet::new()
+            }
+            Some(file_spec) if file_spec != spec => {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                                      format!("The checkpoint belongs to another campaign or dataset: {}", file_spec)));
+            }
+            Some(_) => lines
+                .map(|line| line.trim().parse::<usize>()
+                    .map_err(|_| Error::new(ErrorKind::InvalidData, format!("Invalid fault id in the checkpoint: {}", line))))
+                .collect::<Result<HashSet<usize>>>()?,
+        };
+
+        Ok(Self { file, spec, done })
+    }
+
+    /** specification of the campaign and of its dataset */
+    pub fn get_spec(&self) -> &str { &self.spec }
+
+    /** number of completed faults */
+    pub fn get_num_done(&self) -> usize { self.done.len() }
+
+    pub fn is_done(&self, fault_id: usize) -> bool {
+        self.done.contains(&fault_id)
+    }
+
+    /** it records the fault as completed (marking it again has no effect) */
+    pub fn mark_done(&mut self, fault_id: usize) -> Result<()> {
+        if self.done.insert(fault_id) {
+            writeln!(self.file, "{}", fault_id)?;
+            self.file.sync_data()?;
+        }
+        Ok(())
+    }
+}
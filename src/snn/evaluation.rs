@@ -0,0 +1,47 @@
+/** Evaluation module */
+use crate::configuration::Configuration;
+use crate::neuron::Neuron;
+use crate::snn::network::SNN;
+
+/**
+It classifies a sample from the output spikes of the network ([neuron][instant]).
+Each output neuron is assigned to a label: the predicted label is the one whose neurons fired
+the most, None if no neuron fired (as in `simulation/outputInterface.py`).
+ */
+pub fn classify(output_spikes: &[Vec<u8>], assignments: &[u8]) -> Option<u8> {
+    let num_labels = assignments.iter().max().map_or(0, |max| *max as usize + 1);
+    let mut counts = vec![0u64; num_labels];
+
+    for (neuron_spikes, label) in output_spikes.iter().zip(assignments.iter()) {
+        counts[*label as usize] += neuron_spikes.iter().map(|spike| *spike as u64).sum::<u64>();
+    }
+
+    let mut classification = None;
+    let mut max_count = 0;
+    for (label, count) in counts.into_iter().enumerate() {
+        if count > max_count {
+            max_count = count;
+            classification = Some(label as u8);
+        }
+    }
+    classification
+}
+
+/**
+It runs the network on each sample and returns the percentage of samples classified with their label.
+ */
+pub fn accuracy<N: Neuron + Clone + 'static, R: Configuration + Clone + Send + 'static>(
+    snn: &mut SNN<N, R>, inputs: &[Vec<Vec<u8>>], labels: &[u8], assignments: &[u8]) -> f64 {
+    if inputs.len() != labels.len() {
+        panic!("The number of labels must be equal to the number of input samples");
+    }
+    if inputs.is_empty() {
+        return 0.0;
+    }
+
+    let correct = inputs.iter().zip(labels.iter())
+        .filter(|(input, label)| classify(&snn.process(input), assignments) == Some(**label))
+        .count();
+
+    correct as f64 / inputs.len() as f64 * 100.0
+}
@@ -73,6 +73,36 @@ impl TransientBitFlip {
     pub fn set_bit_changed(&mut self, val: bool) { self.bit_changed = val }
 }
 
+impl Components {
+    /** all the components, each one once */
+    pub const ALL: [Components; 11] = [
+        Components::VTh, Components::VRest, Components::VReset, Components::Tau, Components::VMem, Components::Ts,
+        Components::Dt, Components::Weights, Components::IntraWeights, Components::PrevSpikes, Components::None,
+    ];
+
+    /** name of the component, as written in the campaign specifications and in the results files */
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Components::VTh => "VTh",
+            Components::VRest => "VRest",
+            Components::VReset => "VReset",
+            Components::Tau => "Tau",
+            Components::VMem => "VMem",
+            Components::Ts => "Ts",
+            Components::Dt => "Dt",
+            Components::Weights => "Weights",
+            Components::IntraWeights => "IntraWeights",
+            Components::PrevSpikes => "PrevSpikes",
+            Components::None => "None",
+        }
+    }
+
+    /** component with the given name, None if there is no such component */
+    pub fn from_name(name: &str) -> Option<Self> {
+        Components::ALL.into_iter().find(|component| component.get_name() == name)
+    }
+}
+
 impl Conf {
     pub fn new(components: Vec<Components>, failure: Failure, index_neuron: usize) -> Self {
         Self { components, failure, index_neuron, done:false }
@@ -93,6 +123,16 @@ impl Configuration for Conf {
 }
 
 impl Failure {
+    /** name of the fault model, as written in the results files */
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Failure::StuckAt0(_) => "StuckAt0",
+            Failure::StuckAt1(_) => "StuckAt1",
+            Failure::TransientBitFlip(_) => "TransientBitFlip",
+            Failure::None => "None",
+        }
+    }
+
     pub fn get_position(&self) -> Option<usize> {
         match self {
             Failure::StuckAt0(s) => { Some(s.get_position()) }
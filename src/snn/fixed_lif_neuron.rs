@@ -0,0 +1,139 @@
+/** Fixed point LIF module */
+use crate::neuron::Neuron;
+use crate::snn::fixed_point::FixedFormat;
+
+/**
+Model of LIF Neuron computed in fixed point arithmetic, as in neuromorphic hardware datapaths.
+All the parameters and the membrane potential are words of the given format, the arithmetic saturates
+and the leak is a shift: for each elapsed instant v_mem -= (v_mem - v_rest) >> k, where 2^k is the
+power of two nearest to tau / dt.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedLifNeuron {
+    format: FixedFormat,
+    v_th: i64,      /* Threshold potential */
+    v_rest: i64,    /* Resting potential */
+    v_reset: i64,   /* Reset potential */
+    tau: i64,
+    v_mem: i64,     /* Membrane potential */
+    ts: u64,        /* Last instant in which has been received at least one spike */
+    dt: i64,
+}
+
+impl FixedLifNeuron {
+    pub fn new(v_th: f64, v_rest: f64, v_reset: f64, tau: f64, dt: f64, format: FixedFormat) -> Self {
+        Self {
+            format,
+            v_th: format.to_raw(v_th),
+            v_rest: format.to_raw(v_rest),
+            v_reset: format.to_raw(v_reset),
+            tau: format.to_raw(tau),
+            v_mem: format.to_raw(v_rest),
+            ts: 0u64,
+            dt: format.to_raw(dt),
+        }
+    }
+    pub fn get_format(&self) -> FixedFormat {
+        self.format
+    }
+
+    /* shift of the leak, None if there is no leak (tau or dt not positive) */
+    fn leak_shift(&self) -> Option<u32> {
+        if self.tau <= 0 || self.dt <= 0 {
+            return None;
+        }
+        let ratio = (self.tau / self.dt) as u64;
+        if ratio == 0 {
+            return Some(0);
+        }
+        /* round log2(ratio) to the nearest power of two */
+        let shift = 63 - ratio.leading_zeros();
+        if ratio - (1u64 << shift) > (1u64 << shift) / 2 {
+            Some(shift + 1)
+        } else {
+            Some(shift)
+        }
+    }
+}
+
+impl Neuron for FixedLifNeuron {
+    type Float = f64;
+
+    fn get_v_th(&self) -> f64 {
+        self.format.from_raw(self.v_th)
+    }
+    fn set_v_th(&mut self, new_val: f64) { self.v_th = self.format.to_raw(new_val) }
+    fn calculate_v_mem(&mut self, t: u64, extra_intra_sum: f64) -> u8 {
+        let diff_time = t.abs_diff(self.ts);
+
+        if self.v_mem < self.v_rest {
+            self.v_mem = self.v_rest;
+        }
+        if let Some(shift) = self.leak_shift() {
+            for _ in 0..diff_time {
+                let leak = self.v_mem.saturating_sub(self.v_rest).checked_shr(shift).unwrap_or(0);
+                if leak == 0 {
+                    break;
+                }
+                self.v_mem -= leak;
+            }
+        }
+        self.v_mem = self.format.saturating_add(self.v_mem, self.format.to_raw(extra_intra_sum));
+        self.ts = t;
+        if self.v_mem > self.v_th {
+            self.v_mem = self.v_reset;
+            1
+        } else {
+            0
+        }
+    }
+    fn init(&mut self) {
+        self.v_mem = self.v_rest;
+        self.ts = 0u64;
+    }
+    fn get_tau(&self) -> f64 {
+        self.format.from_raw(self.tau)
+    }
+    fn get_v_reset(&self) -> f64 {
+        self.format.from_raw(self.v_reset)
+    }
+    fn get_v_rest(&self) -> f64 {
+        self.format.from_raw(self.v_rest)
+    }
+    fn get_ts(&self) -> u64 {
+        self.ts
+    }
+    fn get_v_mem(&self) -> f64 {
+        self.format.from_raw(self.v_mem)
+    }
+    fn set_v_mem(&mut self, val: f64) {
+        self.v_mem = self.format.to_raw(val);
+    }
+    fn set_tau(&mut self, val: f64) {
+        self.tau = self.format.to_raw(val);
+    }
+    fn set_v_reset(&mut self, val: f64) {
+        self.v_reset = self.format.to_raw(val);
+    }
+    fn set_v_rest(&mut self, val: f64) {
+        self.v_rest = self.format.to_raw(val);
+    }
+    fn set_ts(&mut self, val: u64) {
+        self.ts = val;
+    }
+    fn get_dt(&self) -> f64 {
+        self.format.from_raw(self.dt)
+    }
+    fn set_dt(&mut self, val: f64) {
+        self.dt = self.format.to_raw(val);
+    }
+    fn get_word_width(&self) -> usize {
+        self.format.get_width()
+    }
+    fn encode_word(&self, val: f64) -> u64 {
+        self.format.to_word(val)
+    }
+    fn decode_word(&self, word: u64) -> f64 {
+        self.format.from_word(word)
+    }
+}
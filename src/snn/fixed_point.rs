@@ -0,0 +1,60 @@
+/** Fixed point module */
+/**
+Signed fixed point format (two's complement) with `int_bits` integer bits (sign included)
+and `frac_bits` fractional bits. Every operation saturates to the range of the format.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedFormat {
+    int_bits: usize,
+    frac_bits: usize,
+}
+
+impl FixedFormat {
+    pub fn new(int_bits: usize, frac_bits: usize) -> Self {
+        if int_bits == 0 || int_bits + frac_bits > 64 {
+            panic!("The fixed point format must have at least the sign bit and at most 64 bits");
+        }
+        Self { int_bits, frac_bits }
+    }
+
+    pub fn get_int_bits(&self) -> usize { self.int_bits }
+    pub fn get_frac_bits(&self) -> usize { self.frac_bits }
+    pub fn get_width(&self) -> usize { self.int_bits + self.frac_bits }
+
+    pub fn get_max_raw(&self) -> i64 { ((1u128 << (self.get_width() - 1)) - 1) as i64 }
+    pub fn get_min_raw(&self) -> i64 { -self.get_max_raw() - 1 }
+
+    /** it converts a real value to the nearest raw value of the format (NaN is converted to 0) */
+    pub fn to_raw(&self, val: f64) -> i64 {
+        if val.is_nan() {
+            return 0;
+        }
+        let scaled = (val * (1u128 << self.frac_bits) as f64).round();
+        (scaled as i64).clamp(self.get_min_raw(), self.get_max_raw())
+    }
+
+    pub fn from_raw(&self, raw: i64) -> f64 {
+        raw as f64 / (1u128 << self.frac_bits) as f64
+    }
+
+    pub fn saturating_add(&self, a: i64, b: i64) -> i64 {
+        a.saturating_add(b).clamp(self.get_min_raw(), self.get_max_raw())
+    }
+
+    /** it returns the word (of `width` bits) storing a real value */
+    pub fn to_word(&self, val: f64) -> u64 {
+        let shift = 64 - self.get_width();
+        ((self.to_raw(val) as u64) << shift) >> shift
+    }
+
+    /** it returns the real value stored in a word (of `width` bits) */
+    pub fn from_word(&self, word: u64) -> f64 {
+        let shift = 64 - self.get_width();
+        self.from_raw(((word << shift) as i64) >> shift)
+    }
+
+    /** it rounds a real value to the nearest one representable in the format */
+    pub fn quantize(&self, val: f64) -> f64 {
+        self.from_raw(self.to_raw(val))
+    }
+}
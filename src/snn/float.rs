@@ -0,0 +1,47 @@
+/** Float module */
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+
+/**
+Trait for the floating point types a network can be built with (f32 and f64).
+Faults are injected into the IEEE-754 word of the type, of `WIDTH` bits.
+ */
+pub trait Float: Copy + Debug + PartialEq + PartialOrd + Send + Sync + 'static
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+    + Neg<Output = Self> + AddAssign {
+    const ZERO: Self;
+    const WIDTH: usize;
+
+    /** IEEE-754 word of the value, in the least significant bits */
+    fn to_word(self) -> u64;
+    fn from_word(word: u64) -> Self;
+    fn to_f64(self) -> f64;
+    fn from_f64(val: f64) -> Self;
+    /** e^self */
+    fn exp(self) -> Self;
+    fn is_nan(self) -> bool;
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+    const WIDTH: usize = 64;
+
+    fn to_word(self) -> u64 { self.to_bits() }
+    fn from_word(word: u64) -> Self { f64::from_bits(word) }
+    fn to_f64(self) -> f64 { self }
+    fn from_f64(val: f64) -> Self { val }
+    fn exp(self) -> Self { std::f64::consts::E.powf(self) }
+    fn is_nan(self) -> bool { f64::is_nan(self) }
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+    const WIDTH: usize = 32;
+
+    fn to_word(self) -> u64 { self.to_bits() as u64 }
+    fn from_word(word: u64) -> Self { f32::from_bits(word as u32) }
+    fn to_f64(self) -> f64 { self as f64 }
+    fn from_f64(val: f64) -> Self { val as f32 }
+    fn exp(self) -> Self { std::f32::consts::E.powf(self) }
+    fn is_nan(self) -> bool { f32::is_nan(self) }
+}
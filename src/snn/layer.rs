@@ -1,24 +1,39 @@
 use std::sync::mpsc::{Receiver, Sender};
 use crate::snn::neuron::Neuron;
-use crate::snn::spike_event::SpikeEvent;
+use crate::snn::spike_event::{SpikeEvent, SpikeMessage};
+use crate::snn::spike_vec::SpikeVec;
 use crate::snn::configuration::Configuration;
 use crate::failure::{Components, Failure};
+use crate::snn::range_guard::{GuardAction, RangeGuard};
+use crate::snn::protection::{ProtectedWeights, WeightProtection};
+use crate::snn::fixed_point::FixedFormat;
+use crate::snn::float::Float;
+use crate::snn::sparse::{SparseFormat, SparseMatrix};
+use crate::snn::probe::{Probe, ProbeSample};
+use crate::snn::recording::SpikeRecord;
 use bit::BitIndex;
 
 #[derive(Debug)]
 pub struct Layer<N: Neuron + Clone + Send + 'static, R: Configuration + Clone + Send + 'static> {
     neurons: Vec<N>,
-    weights: Vec<Vec<f64>>,
-    intra_weights: Vec<Vec<f64>>,
-    prev_spikes: Vec<u8>,
+    weights: Vec<Vec<N::Float>>,
+    intra_weights: Vec<Vec<N::Float>>,
+    prev_spikes: SpikeVec,
     configuration: R,
+    range_guard: Option<RangeGuard<N::Float>>,
+    weights_protection: Option<ProtectedWeights>,
+    intra_weights_protection: Option<ProtectedWeights>,
+    weights_format: Option<FixedFormat>,
+    sparse_weights: Option<SparseMatrix<N::Float>>,
+    probes: Vec<Probe<N::Float>>,
+    spike_record: Option<SpikeRecord>,
 }
 
 impl<N: Neuron + Clone + Send + 'static, R: Configuration + Clone + Send + 'static> Layer<N, R> {
     pub fn new(
         neurons: Vec<N>,
-        weights: Vec<Vec<f64>>,
-        intra_weights: Vec<Vec<f64>>,
+        weights: Vec<Vec<N::Float>>,
+        intra_weights: Vec<Vec<N::Float>>,
         configuration: R,
     ) -> Self {
         let num_neurons = neurons.len();
@@ -26,8 +41,15 @@ impl<N: Neuron + Clone + Send + 'static, R: Configuration + Clone + Send + 'stat
             neurons,
             weights,
             intra_weights,
-            prev_spikes: vec![0; num_neurons],
+            prev_spikes: SpikeVec::new(num_neurons),
             configuration,
+            range_guard: None,
+            weights_protection: None,
+            intra_weights_protection: None,
+            weights_format: None,
+            sparse_weights: None,
+            probes: Vec::new(),
+            spike_record: None,
         }
     }
 
@@ -36,17 +58,144 @@ impl<N: Neuron + Clone + Send + 'static, R: Configuration + Clone + Send + 'stat
         self.neurons.len()
     }
     pub fn get_neurons(&self) -> Vec<N> { self.neurons.clone() }
-    pub fn get_weights(&self) -> Vec<Vec<f64>> {
-        self.weights.clone()
+    pub fn get_weights(&self) -> Vec<Vec<N::Float>> {
+        match self.sparse_weights.as_ref() {
+            Some(sparse) => sparse.to_dense(),
+            None => self.weights.clone(),
+        }
+    }
+    /** number of inputs of the layer (columns of the weights matrix) */
+    pub fn get_input_dimension(&self) -> usize {
+        match self.sparse_weights.as_ref() {
+            Some(sparse) => sparse.get_cols(),
+            None => self.weights.first().map_or(0, |row| row.len()),
+        }
     }
-    pub fn get_intra_weights(&self) -> Vec<Vec<f64>> {
+    pub fn get_intra_weights(&self) -> Vec<Vec<N::Float>> {
         self.intra_weights.clone()
     }
-    pub fn get_prev_spikes(&self) -> Vec<u8> { self.prev_spikes.clone() }
+    pub fn get_prev_spikes(&self) -> &SpikeVec { &self.prev_spikes }
     pub fn get_configuration(&self) -> R { self.configuration.clone() }
-    pub fn set_intra_weights(&mut self, val: Vec<Vec<f64>>) { self.intra_weights = val }
-    pub fn set_weights(&mut self, val: Vec<Vec<f64>>) { self.weights = val }
-    pub fn set_prev_spikes(&mut self, val: Vec<u8>) { self.prev_spikes = val }
+    pub fn set_intra_weights(&mut self, val: Vec<Vec<N::Float>>) { self.intra_weights = val }
+    pub fn set_weights(&mut self, val: Vec<Vec<N::Float>>) {
+        match self.sparse_weights.as_ref().map(|sparse| sparse.get_format()) {
+            Some(format) => self.sparse_weights = Some(SparseMatrix::from_dense(&val, format)),
+            None => self.weights = val,
+        }
+    }
+    pub fn set_prev_spikes(&mut self, val: impl Into<SpikeVec>) { self.prev_spikes = val.into() }
+    pub fn get_range_guard(&self) -> Option<RangeGuard<N::Float>> { self.range_guard.clone() }
+    pub fn set_range_guard(&mut self, val: Option<RangeGuard<N::Float>>) { self.range_guard = val }
+
+    /**
+    It enables the protection of the neuron parameters and of the weights, taking the valid ranges
+    and the golden values from the current (fault-free) state of the layer.
+     */
+    pub fn enable_range_guard(&mut self, margin: f64, action: GuardAction) {
+        self.range_guard = Some(RangeGuard::new(&self.neurons, self.get_stored_weights(), &self.intra_weights, margin, action));
+    }
+    pub fn get_sparse_weights(&self) -> Option<SparseMatrix<N::Float>> { self.sparse_weights.clone() }
+    pub fn get_probes(&self) -> &[Probe<N::Float>] { &self.probes }
+
+    pub fn get_spike_record(&self) -> Option<&SpikeRecord> { self.spike_record.as_ref() }
+
+    /**
+    It enables the recording of the output spikes of the layer, reset at each run.
+     */
+    pub fn enable_spike_recording(&mut self) {
+        self.spike_record = Some(SpikeRecord::new(self.neurons.len()));
+    }
+
+    /**
+    It attaches a probe to a neuron of the layer: from now on the state of the neuron is recorded
+    at each instant processed by the layer.
+     */
+    pub fn add_probe(&mut self, probe: Probe<N::Float>) {
+        if probe.get_neuron() >= self.neurons.len() {
+            panic!("The probed neuron {} does not exist in a layer of {} neurons", probe.get_neuron(), self.neurons.len());
+        }
+        self.probes.push(probe);
+    }
+
+    /**
+    It stores the weights as a sparse matrix in the given format, keeping only the non-zero values:
+    the weighted sums skip the zero weights and the faults on Weights address the stored values only.
+    Range guard and protection work on the stored values, so they must be enabled after this.
+     */
+    pub fn enable_sparse_weights(&mut self, format: SparseFormat) {
+        let weights = self.get_weights();
+        self.sparse_weights = Some(SparseMatrix::from_dense(&weights, format));
+        self.weights = Vec::new();
+
+        /* the check bits must be computed on the stored values */
+        if let Some(protection) = self.weights_protection.as_ref().map(|protection| protection.get_protection()) {
+            self.enable_weights_protection(protection);
+        }
+    }
+
+    /* words storing the weights: the rows of the dense matrix or the non-zero values of the sparse one, as a single row */
+    fn get_stored_weights(&self) -> &[Vec<N::Float>] {
+        match self.sparse_weights.as_ref() {
+            Some(sparse) => std::slice::from_ref(sparse.get_values()),
+            None => &self.weights,
+        }
+    }
+    fn get_stored_weights_mut(&mut self) -> &mut [Vec<N::Float>] {
+        match self.sparse_weights.as_mut() {
+            Some(sparse) => std::slice::from_mut(sparse.get_values_mut()),
+            None => &mut self.weights,
+        }
+    }
+    /* index in the stored weights of the word hit by a fault in the given bit position */
+    fn weights_fault_index(&self, position: usize, width: usize) -> (usize, usize) {
+        match self.sparse_weights.as_ref() {
+            Some(sparse) => (0, (position / width) % sparse.get_nnz().max(1)),
+            None => fault_matrix_index(position, self.weights.len(), width),
+        }
+    }
+    pub fn get_weights_format(&self) -> Option<FixedFormat> { self.weights_format }
+
+    /**
+    It stores weights and intra weights as fixed point words of the given format: the current values are
+    quantized and the faults on Weights and IntraWeights are injected into words of the format width.
+     */
+    pub fn enable_fixed_point_weights(&mut self, format: FixedFormat) {
+        self.weights_format = Some(format);
+        self.get_stored_weights_mut().iter_mut().flatten().for_each(|weight| *weight = N::Float::from_f64(format.quantize(weight.to_f64())));
+        self.intra_weights.iter_mut().flatten().for_each(|weight| *weight = N::Float::from_f64(format.quantize(weight.to_f64())));
+
+        /* the check bits must be computed on the new words */
+        if let Some(protection) = self.weights_protection.as_ref().map(|protection| protection.get_protection()) {
+            self.enable_weights_protection(protection);
+        }
+    }
+
+    /* width of the words storing the weights */
+    fn get_weight_width(&self) -> usize {
+        self.weights_format.map_or(N::Float::WIDTH, |format| format.get_width())
+    }
+    fn weight_to_word(&self, val: N::Float) -> u64 {
+        self.weights_format.map_or(val.to_word(), |format| format.to_word(val.to_f64()))
+    }
+    fn weight_from_word(&self, word: u64) -> N::Float {
+        self.weights_format.map_or(N::Float::from_word(word), |format| N::Float::from_f64(format.from_word(word)))
+    }
+
+    pub fn get_weights_protection(&self) -> Option<ProtectedWeights> { self.weights_protection.clone() }
+    pub fn get_intra_weights_protection(&self) -> Option<ProtectedWeights> { self.intra_weights_protection.clone() }
+
+    /**
+    It enables the protected storage of weights and intra weights, computing the check bits
+    from the current (fault-free) matrices. Faults on Weights and IntraWeights then pass through the decoder.
+     */
+    pub fn enable_weights_protection(&mut self, protection: WeightProtection) {
+        let weights: Vec<Vec<u64>> = self.get_stored_weights().iter()
+            .map(|row| row.iter().map(|weight| self.weight_to_word(*weight)).collect()).collect();
+        let intra_weights: Vec<Vec<u64>> = self.intra_weights.iter()
+            .map(|row| row.iter().map(|weight| self.weight_to_word(*weight)).collect()).collect();
+        self.weights_protection = Some(ProtectedWeights::new(protection, &weights));
+        self.intra_weights_protection = Some(ProtectedWeights::new(protection, &intra_weights));
+    }
 
     fn generate_faults(&mut self) {
         /* if there is at least one component to fail, search the selected component to keep it broken */
@@ -60,50 +209,63 @@ impl<N: Neuron + Clone + Send + 'static, R: Configuration + Clone + Send + 'stat
 
             match component {
                 Components::VTh => {
-                    let new_val = modify_bits(failure, neuron.get_v_th().to_bits());
-                    neuron.set_v_th(f64::from_bits(new_val));
+                    let new_val = modify_bits_width(failure, neuron.encode_word(neuron.get_v_th()), neuron.get_word_width());
+                    neuron.set_v_th(neuron.decode_word(new_val));
                 }
                 Components::VRest => {
-                    let new_val = modify_bits(failure, neuron.get_v_rest().to_bits());
-                    neuron.set_v_rest(f64::from_bits(new_val));
+                    let new_val = modify_bits_width(failure, neuron.encode_word(neuron.get_v_rest()), neuron.get_word_width());
+                    neuron.set_v_rest(neuron.decode_word(new_val));
                 }
                 Components::VReset => {
-                    let new_val = modify_bits(failure, neuron.get_v_reset().to_bits());
-                    neuron.set_v_reset(f64::from_bits(new_val));
+                    let new_val = modify_bits_width(failure, neuron.encode_word(neuron.get_v_reset()), neuron.get_word_width());
+                    neuron.set_v_reset(neuron.decode_word(new_val));
                 }
                 Components::Tau => {
-                    let new_val = modify_bits(failure, neuron.get_tau().to_bits());
-                    neuron.set_tau(f64::from_bits(new_val));
+                    let new_val = modify_bits_width(failure, neuron.encode_word(neuron.get_tau()), neuron.get_word_width());
+                    neuron.set_tau(neuron.decode_word(new_val));
                 }
                 Components::VMem => {
-                    let new_val = modify_bits(failure, neuron.get_v_mem().to_bits());
-                    neuron.set_v_mem(f64::from_bits(new_val));
+                    let new_val = modify_bits_width(failure, neuron.encode_word(neuron.get_v_mem()), neuron.get_word_width());
+                    neuron.set_v_mem(neuron.decode_word(new_val));
                 }
                 Components::Ts => {
                     let new_val = modify_bits(failure, neuron.get_ts());
                     neuron.set_ts(new_val);
                 }
                 Components::Dt => {
-                    let new_val = modify_bits(failure, neuron.get_dt().to_bits());
-                    neuron.set_dt(f64::from_bits(new_val));
+                    let new_val = modify_bits_width(failure, neuron.encode_word(neuron.get_dt()), neuron.get_word_width());
+                    neuron.set_dt(neuron.decode_word(new_val));
                 }
                 Components::IntraWeights => {
                     let mut matrix = self.intra_weights.clone();
-                    let i = (failure.get_position().unwrap() / 64) / matrix.len();
-                    let j = (failure.get_position().unwrap() / 64) % matrix.len();
+                    let width = self.get_weight_width();
+                    let (i, j) = fault_matrix_index(failure.get_position().unwrap(), matrix.len(), width);
 
-                    let new_val = modify_bits(failure, matrix[i][j].to_bits());
-                    matrix[i][j] = f64::from_bits(new_val);
+                    let old_word = self.weight_to_word(matrix[i][j]);
+                    let new_word = modify_bits_width(failure, old_word, width);
+                    matrix[i][j] = match self.intra_weights_protection.as_mut() {
+                        Some(protection) => protection.read(i, j, old_word, new_word)
+                            .map_or(N::Float::ZERO, |word| self.weight_from_word(word)),
+                        None => self.weight_from_word(new_word),
+                    };
                     self.intra_weights = matrix;
                 }
                 Components::Weights => {
-                    let mut matrix = self.weights.clone();
-                    let i = (failure.get_position().unwrap() / 64) / matrix.len();
-                    let j = (failure.get_position().unwrap() / 64) % matrix.len();
+                    let mut matrix = self.get_stored_weights().to_vec();
+                    let width = self.get_weight_width();
+                    let (i, j) = self.weights_fault_index(failure.get_position().unwrap(), width);
+                    if matrix.get(i).and_then(|row| row.get(j)).is_none() {
+                        continue; /* sparse matrix without stored values */
+                    }
 
-                    let new_val = modify_bits(failure, matrix[i][j].to_bits());
-                    matrix[i][j] = f64::from_bits(new_val);
-                    self.weights = matrix;
+                    let old_word = self.weight_to_word(matrix[i][j]);
+                    let new_word = modify_bits_width(failure, old_word, width);
+                    matrix[i][j] = match self.weights_protection.as_mut() {
+                        Some(protection) => protection.read(i, j, old_word, new_word)
+                            .map_or(N::Float::ZERO, |word| self.weight_from_word(word)),
+                        None => self.weight_from_word(new_word),
+                    };
+                    self.get_stored_weights_mut().clone_from_slice(&matrix);
                 }
                 Components::PrevSpikes => {
                     let modified_prev_spikes = self.fault_prev_spikes(&failure);
@@ -113,27 +275,119 @@ impl<N: Neuron + Clone + Send + 'static, R: Configuration + Clone + Send + 'stat
             }
         }
     }
-    pub fn fault_prev_spikes(&self, failure: &Failure) -> Vec<u8> {
-        let mut vec = self.get_prev_spikes();
+    /**
+    It checks whether the fault of the configuration can change the behaviour of the layer,
+    so that provably masked injections can be skipped. This is a conservative static check on the
+    fault-free state of the layer, made before the simulation and not at the instant of the injection:
+    a fault it reports as effective may still turn out to be masked.
+    - A StuckAt fault on a bit that already holds the stuck value is masked
+    - VMem, Ts and PrevSpikes are rewritten at every instant and the fault is injected again each time,
+      so they are masked only when the parameters reading them make them irrelevant
+      (Ts without leak, PrevSpikes of a neuron with no outgoing intra weights)
+    - Ts only holds the instants of the sample, so a fault on it is masked when it changes none of the
+      `duration` instants (e.g. a StuckAt0 on a bit worth more than the last instant)
+    - A TransientBitFlip always changes the target word
+     */
+    pub fn is_fault_effective(&self, duration: u64) -> bool {
+        let failure = self.configuration.get_failure();
+        if failure == Failure::None {
+            return false;
+        }
+
+        let index = self.configuration.get_index_neuron();
+        let neuron = match self.neurons.get(index) {
+            Some(neuron) => neuron,
+            None => return true, /* not provable, let the simulation report the error */
+        };
+
+        self.configuration.get_vec_components().into_iter().any(|component| {
+            let (word, width) = match component {
+                Components::VTh => (neuron.encode_word(neuron.get_v_th()), neuron.get_word_width()),
+                Components::VRest => (neuron.encode_word(neuron.get_v_rest()), neuron.get_word_width()),
+                Components::VReset => (neuron.encode_word(neuron.get_v_reset()), neuron.get_word_width()),
+                Components::Tau => (neuron.encode_word(neuron.get_tau()), neuron.get_word_width()),
+                Components::Dt => (neuron.encode_word(neuron.get_dt()), neuron.get_word_width()),
+                Components::IntraWeights | Components::Weights => {
+                    let matrix = if component == Components::Weights { self.get_stored_weights() } else { &self.intra_weights };
+                    if matrix.is_empty() {
+                        return true;
+                    }
+                    let width = self.get_weight_width();
+                    let (i, j) = if component == Components::Weights {
+                        self.weights_fault_index(failure.get_position().unwrap(), width)
+                    } else {
+                        fault_matrix_index(failure.get_position().unwrap(), matrix.len(), width)
+                    };
+                    match matrix.get(i).and_then(|row| row.get(j)) {
+                        Some(weight) => (self.weight_to_word(*weight), width),
+                        /* a sparse matrix without stored values cannot be hit */
+                        None => return self.sparse_weights.is_none() || component == Components::IntraWeights,
+                    }
+                }
+                /* without leak the last instant is never read */
+                Components::Ts => return neuron.get_tau() != N::Float::ZERO && neuron.get_dt() != N::Float::ZERO
+                    && (0..duration).any(|ts| modify_bits(failure.clone(), ts) != ts),
+                Components::PrevSpikes => {
+                    let n = self.neurons.len();
+                    if n == 0 {
+                        return false;
+                    }
+                    /* the spike of neuron i only reaches the others through the column i of the intra weights */
+                    let i = failure.get_position().unwrap() % n;
+                    return self.intra_weights.iter().enumerate()
+                        .any(|(k, row)| k != i && row.get(i).is_some_and(|weight| *weight != N::Float::ZERO));
+                }
+                Components::VMem => return true,
+                Components::None => return false,
+            };
+
+            match failure {
+                Failure::StuckAt0(_) | Failure::StuckAt1(_) => modify_bits_width(failure.clone(), word, width) != word,
+                _ => true,
+            }
+        })
+    }
+
+    /**
+    It returns the (row, column) of the weight hit by the fault of the configuration,
+    None if the fault is not on Weights or IntraWeights (or the sparse weights store no values).
+     */
+    pub fn get_fault_weight_position(&self) -> Option<(usize, usize)> {
+        let position = self.configuration.get_failure().get_position()?;
+        let width = self.get_weight_width();
+        let components = self.configuration.get_vec_components();
+
+        if components.contains(&Components::Weights) {
+            let (i, j) = self.weights_fault_index(position, width);
+            match self.sparse_weights.as_ref() {
+                Some(sparse) if sparse.get_nnz() > 0 => Some(sparse.get_position(j)),
+                Some(_) => None,
+                None => Some((i, j)),
+            }
+        } else if components.contains(&Components::IntraWeights) {
+            Some(fault_matrix_index(position, self.intra_weights.len(), width))
+        } else {
+            None
+        }
+    }
+
+    pub fn fault_prev_spikes(&self, failure: &Failure) -> SpikeVec {
+        let mut vec = self.get_prev_spikes().clone();
 
-        if vec.len() > 0 {
+        if !vec.is_empty() {
             let i = failure.get_position().unwrap() % vec.len();
 
             match failure {
                 Failure::StuckAt0(_) => {
-                    if vec[i] == 1 {
-                        vec[i] = 0;
-                    }
+                    vec.set(i, 0);
                 }
                 Failure::StuckAt1(_) => {
-                    if vec[i] == 0 {
-                        vec[i] = 1;
-                    }
+                    vec.set(i, 1);
                 }
                 Failure::TransientBitFlip(t) => {
                     let changed = t.get_bit_changed();
                     if !changed {
-                        vec[i] = 1 - vec[i];
+                        vec.set(i, 1 - vec.get(i));
                     }
                 }
                 _ => {}
@@ -143,7 +397,35 @@ impl<N: Neuron + Clone + Send + 'static, R: Configuration + Clone + Send + 'stat
         vec
     }
 
-    fn generate_spike(&mut self, input_spike_event: &SpikeEvent, instant: u64, output_spikes: &mut Vec<u8>, at_least_one_spike: &mut bool) {
+    /* it checks against its valid range the word hit by the fault, for the components covered by the range guard */
+    fn check_fault_range(&mut self) {
+        let Some(position) = self.configuration.get_failure().get_position() else { return };
+        let Some(mut guard) = self.range_guard.take() else { return };
+        let index = self.configuration.get_index_neuron();
+        let width = self.get_weight_width();
+
+        for component in self.configuration.get_vec_components() {
+            match component {
+                Components::VTh | Components::VRest | Components::VReset | Components::Tau | Components::Dt => {
+                    if let Some(neuron) = self.neurons.get_mut(index) {
+                        guard.check_neuron(index, neuron);
+                    }
+                }
+                Components::Weights => {
+                    let weight_index = self.weights_fault_index(position, width);
+                    guard.check_weight(weight_index, self.get_stored_weights_mut());
+                }
+                Components::IntraWeights => {
+                    let intra_weight_index = fault_matrix_index(position, self.intra_weights.len(), width);
+                    guard.check_intra_weight(intra_weight_index, &mut self.intra_weights);
+                }
+                _ => {}
+            }
+        }
+        self.range_guard = Some(guard);
+    }
+
+    fn generate_spike(&mut self, input_spike_event: &SpikeEvent, instant: u64, output_spikes: &mut SpikeVec, at_least_one_spike: &mut bool) {
         /* generate FAULTS according to the configuration */
         if self.configuration.get_done() == false && self.configuration.get_failure() != Failure::None  {
             self.generate_faults();
@@ -151,37 +433,43 @@ impl<N: Neuron + Clone + Send + 'static, R: Configuration + Clone + Send + 'stat
              && !self.configuration.get_vec_components().contains(&Components::PrevSpikes) {
                 self.configuration.set_done(true);
             }
+            /* check the value hit by the fault against its valid range, if the protection is enabled
+            (only the injection of a fault can change it) */
+            self.check_fault_range();
         }
-        /* for each neuron compute the sums of intra weights, extra weights and v_mem */
-        for (index, neuron) in self.neurons.iter_mut().enumerate() {
-            let events = input_spike_event.get_spikes();
-
-            /* compute extra weighted sum */
-            let mut extra_weighted_sum = 0f64;
-            let extra_weights_pairs = self.weights[index].iter().zip(events.iter());
-            for (weight, spike) in extra_weights_pairs {
-                if *spike != 0 {
-                    extra_weighted_sum += *weight;
-                }
-            }
+        /* extra weighted sums: scatter the columns of the inputs which fired */
+        let extra_weighted_sums = match self.sparse_weights.as_ref() {
+            Some(sparse) => sparse.spike_sums(input_spike_event),
+            None => scatter_columns(&self.weights, self.neurons.len(), input_spike_event.get_active(), false),
+        };
 
-            /* compute intra weighted sum */
-            let prev_events = self.prev_spikes.clone();
-            let mut intra_weighted_sum = 0f64;
-            let intra_weights_pairs = self.intra_weights[index].iter().zip(prev_events.iter());
-            for (i, (weight, spike)) in intra_weights_pairs.enumerate() {
-                /* skip the reflexive link */
-                if i != index && *spike != 0 {
-                    intra_weighted_sum += *weight;
-                }
-            }
+        /* intra weighted sums: scatter the columns of the neurons which fired in the previous instant,
+        skipping the reflexive links */
+        let intra_weighted_sums = scatter_columns(&self.intra_weights, self.neurons.len(), self.prev_spikes.iter_ones(), true);
 
-            let neuron_spike = neuron.calculate_v_mem(instant, extra_weighted_sum + intra_weighted_sum);
+        /* for each neuron compute v_mem */
+        for (index, neuron) in self.neurons.iter_mut().enumerate() {
+            let neuron_spike = neuron.calculate_v_mem(instant, extra_weighted_sums[index] + intra_weighted_sums[index]);
             output_spikes.push(neuron_spike);
             if neuron_spike == 1u8 {
                 *at_least_one_spike = true;
             }
         }
+
+        /* record the state of the probed neurons */
+        for probe in self.probes.iter_mut() {
+            let index = probe.get_neuron();
+            let neuron = &self.neurons[index];
+            probe.record(ProbeSample {
+                instant,
+                v_mem: neuron.get_v_mem(),
+                ts: neuron.get_ts(),
+                v_th: neuron.get_v_th(),
+                extra_sum: extra_weighted_sums[index],
+                intra_sum: intra_weighted_sums[index],
+                spike: output_spikes.get(index),
+            });
+        }
     }
 
     pub fn process(&mut self, layer_input_rc: Receiver<SpikeEvent>, layer_output_tx: Sender<SpikeEvent>) {
@@ -190,32 +478,68 @@ impl<N: Neuron + Clone + Send + 'static, R: Configuration + Clone + Send + 'stat
 
         /* listen to SpikeEvent(s) coming from the previous layer and process them */
         while let Ok(input_spike_event) = layer_input_rc.recv() {
-            /* time instant of the input spike */
-            let instant = input_spike_event.get_ts();
+            /* at least one neuron fired -> send output spikes to the next layer */
+            if let Some(output_spike_event) = self.process_event(&input_spike_event) {
+                let instant = output_spike_event.get_ts();
+                layer_output_tx.send(output_spike_event)
+                    .unwrap_or_else(|_| panic!("Unexpected error sending input spike event t={}", instant))
+            }
+        }
+    }
 
-            /* flag to manage not firing layer */
-            let mut at_least_one_spike = false;
+    /**
+    It processes a stream of samples separated by `SpikeMessage::Reset` markers:
+    at each marker the state of the layer is reset (as at the start of `process`) and the marker is forwarded.
+     */
+    pub fn process_stream(&mut self, layer_input_rc: Receiver<SpikeMessage>, layer_output_tx: Sender<SpikeMessage>) {
+        self.init();
 
-            let mut output_spikes = Vec::<u8>::with_capacity(self.neurons.len());
+        while let Ok(message) = layer_input_rc.recv() {
+            let output_message = match message {
+                SpikeMessage::Event(input_spike_event) => match self.process_event(&input_spike_event) {
+                    Some(output_spike_event) => SpikeMessage::Event(output_spike_event),
+                    None => continue,
+                },
+                SpikeMessage::Reset => {
+                    self.init();
+                    SpikeMessage::Reset
+                }
+            };
+            layer_output_tx.send(output_message)
+                .unwrap_or_else(|_| panic!("Unexpected error sending a message to the next layer"))
+        }
+    }
 
-            /* this function compute the v_mem for all the neurons considering the input spikes
-            and if there is configuration field it provides to apply the fault */
-            self.generate_spike(&input_spike_event, instant, &mut output_spikes, &mut at_least_one_spike);
+    /**
+    It processes the input spikes of one instant and returns the output SpikeEvent,
+    None if no neuron of the layer fired.
+     */
+    pub fn process_event(&mut self, input_spike_event: &SpikeEvent) -> Option<SpikeEvent> {
+        /* time instant of the input spike */
+        let instant = input_spike_event.get_ts();
 
-            /* save output spikes for later */
-            self.prev_spikes = output_spikes.clone();
+        /* flag to manage not firing layer */
+        let mut at_least_one_spike = false;
 
-            /* check if at least one neuron fired - if not, not send any spike */
-            if !at_least_one_spike {
-                continue;
-            }
+        let mut output_spikes = SpikeVec::default();
 
-            /* at least one neuron fired -> send output spikes to the next layer */
-            let output_spike_event = SpikeEvent::new(instant, output_spikes);
+        /* this function compute the v_mem for all the neurons considering the input spikes
+        and if there is configuration field it provides to apply the fault */
+        self.generate_spike(input_spike_event, instant, &mut output_spikes, &mut at_least_one_spike);
+
+        /* save output spikes for later */
+        self.prev_spikes = output_spikes.clone();
+
+        /* check if at least one neuron fired - if not, not send any spike */
+        if !at_least_one_spike {
+            return None;
+        }
 
-            layer_output_tx.send(output_spike_event)
-                .unwrap_or_else(|_| panic!("Unexpected error sending input spike event t={}", instant))
+        let output_spike_event = SpikeEvent::new(instant, output_spikes);
+        if let Some(record) = self.spike_record.as_mut() {
+            record.record(&output_spike_event);
         }
+        Some(output_spike_event)
     }
 
     pub fn init(&mut self) {
@@ -224,19 +548,70 @@ impl<N: Neuron + Clone + Send + 'static, R: Configuration + Clone + Send + 'stat
 
         /* reset neurons */
         self.neurons.iter_mut().for_each(|neuron| neuron.init());
+
+        /* reset counters of the protection */
+        if let Some(guard) = self.range_guard.as_mut() {
+            guard.init();
+        }
+        for protection in [self.weights_protection.as_mut(), self.intra_weights_protection.as_mut()].into_iter().flatten() {
+            protection.init();
+        }
+
+        /* remove the samples and the spikes recorded in the previous run */
+        self.probes.iter_mut().for_each(|probe| probe.clear());
+        if let Some(record) = self.spike_record.as_mut() {
+            record.clear();
+        }
     }
 }
 
-pub fn modify_bits(failure: Failure, mut val: u64) -> u64 {
+/**
+It sums, for each of the `rows` rows of the matrix, the elements in the `active` columns (in increasing order).
+The cost is proportional to the number of active columns; with `skip_diagonal` the elements [i][i] are ignored.
+ */
+fn scatter_columns<F: Float>(matrix: &[Vec<F>], rows: usize, active: impl Iterator<Item = usize>, skip_diagonal: bool) -> Vec<F> {
+    let mut sums = vec![F::ZERO; rows];
+    for j in active {
+        for (i, (sum, row)) in sums.iter_mut().zip(matrix.iter()).enumerate() {
+            if skip_diagonal && i == j {
+                continue;
+            }
+            if let Some(weight) = row.get(j) {
+                *sum += *weight;
+            }
+        }
+    }
+    sums
+}
+
+/**
+It returns the (row, column) of the matrix element hit by a fault in the given bit position,
+for a matrix of words of `width` bits.
+ */
+pub fn fault_matrix_index(position: usize, rows: usize, width: usize) -> (usize, usize) {
+    let i = (position / width) / rows;
+    let j = (position / width) % rows;
+    (i, j)
+}
+
+pub fn modify_bits(failure: Failure, val: u64) -> u64 {
+    modify_bits_width(failure, val, 64)
+}
+
+/**
+It applies the failure to a word of `width` bits (stored in the least significant bits of `val`).
+Position 0 is the most significant bit of the word.
+ */
+pub fn modify_bits_width(failure: Failure, mut val: u64, width: usize) -> u64 {
     let mut position = failure.get_position().unwrap();
 
     /* check if position of the bit is valid */
-    if position >= 64 {
-        position = position % 64;
+    if position >= width {
+        position = position % width;
     }
 
     /* just correct the position with constant */
-    position = 63 - position;
+    position = width - 1 - position;
 
     /* match the type of failure { StuckAt0, StuckAt1, TransientBitFlip } */
     match failure {
@@ -268,6 +643,13 @@ impl<N: Neuron + Clone + Send + 'static, R: Configuration + Clone + Send + 'stat
             intra_weights: self.intra_weights.clone(),
             prev_spikes: self.prev_spikes.clone(),
             configuration: self.configuration.clone(),
+            range_guard: self.range_guard.clone(),
+            weights_protection: self.weights_protection.clone(),
+            intra_weights_protection: self.intra_weights_protection.clone(),
+            weights_format: self.weights_format,
+            sparse_weights: self.sparse_weights.clone(),
+            probes: self.probes.clone(),
+            spike_record: self.spike_record.clone(),
         }
     }
 }
\ No newline at end of file
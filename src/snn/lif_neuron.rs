@@ -1,21 +1,21 @@
 /** LIF module */
 use crate::neuron::Neuron;
-use std::f64::consts::E;
+use crate::snn::float::Float;
 
-/** Model of LIF Neuron (Leaky Integrate-and-Fire) */
+/** Model of LIF Neuron (Leaky Integrate-and-Fire), in f64 (default) or f32 precision */
 #[derive(Debug, Clone, PartialEq)]
-pub struct LifNeuron {
-    v_th: f64,      /* Threshold potential */
-    v_rest: f64,    /* Resting potential */
-    v_reset: f64,   /* Reset potential */
-    tau: f64,
-    v_mem: f64,     /* Membrane potential */
+pub struct LifNeuron<F: Float = f64> {
+    v_th: F,        /* Threshold potential */
+    v_rest: F,      /* Resting potential */
+    v_reset: F,     /* Reset potential */
+    tau: F,
+    v_mem: F,       /* Membrane potential */
     ts: u64,        /* Last instant in which has been received at least one spike */
-    dt: f64,
+    dt: F,
 }
 
-impl LifNeuron {
-    pub fn new(v_th: f64, v_rest: f64, v_reset: f64, tau: f64, dt: f64) -> Self {
+impl<F: Float> LifNeuron<F> {
+    pub fn new(v_th: F, v_rest: F, v_reset: F, tau: F, dt: F) -> Self {
         Self {
             v_th,
             v_rest,
@@ -26,50 +26,52 @@ impl LifNeuron {
             dt,
         }
     }
-    pub fn get_v_mem(&self) -> f64 {
+    pub fn get_v_mem(&self) -> F {
         self.v_mem
     }
     pub fn get_ts(&self) -> u64 {
         self.ts
     }
-    pub fn get_v_th(&self) -> f64 {
+    pub fn get_v_th(&self) -> F {
         self.v_th
     }
-    pub fn get_v_rest(&self) -> f64 {
+    pub fn get_v_rest(&self) -> F {
         self.v_rest
     }
-    pub fn get_v_reset(&self) -> f64 {
+    pub fn get_v_reset(&self) -> F {
         self.v_reset
     }
-    pub fn get_tau(&self) -> f64 {
+    pub fn get_tau(&self) -> F {
         self.tau
     }
-    pub fn get_dt(&self) -> f64 {
+    pub fn get_dt(&self) -> F {
         self.dt
     }
 }
 
-impl Neuron for LifNeuron {
-    fn get_v_th(&self) -> f64 {
+impl<F: Float> Neuron for LifNeuron<F> {
+    type Float = F;
+
+    fn get_v_th(&self) -> F {
         self.v_th
     }
-    fn set_v_th(&mut self, new_val: f64) { self.v_th = new_val }
-    fn calculate_v_mem(&mut self, t: u64, extra_intra_sum: f64) -> u8 {
+    fn set_v_th(&mut self, new_val: F) { self.v_th = new_val }
+    fn calculate_v_mem(&mut self, t: u64, extra_intra_sum: F) -> u8 {
         let diff_time;
         if t > self.ts {
-            diff_time = (t - self.ts) as f64;
+            diff_time = F::from_f64((t - self.ts) as f64);
         }
         else {
-            diff_time = (self.ts - t) as f64;
+            diff_time = F::from_f64((self.ts - t) as f64);
         }
-        let mut exponent = 0.0;
-        if diff_time != 0.0 && self.tau != 0.0 {
+        let mut exponent = F::ZERO;
+        if diff_time != F::ZERO && self.tau != F::ZERO {
             exponent = -(diff_time * self.dt / self.tau);
         }
         if self.v_mem < self.v_rest {
             self.v_mem = self.v_rest;
         }
-        self.v_mem = self.v_rest + (self.v_mem - self.v_rest) * E.powf(exponent) + extra_intra_sum;
+        self.v_mem = self.v_rest + (self.v_mem - self.v_rest) * exponent.exp() + extra_intra_sum;
         self.ts = t;
         if self.v_mem > self.v_th {
             self.v_mem = self.v_reset;
@@ -82,41 +84,41 @@ impl Neuron for LifNeuron {
         self.v_mem = self.v_rest;
         self.ts = 0u64;
     }
-    fn get_tau(&self) -> f64 {
+    fn get_tau(&self) -> F {
         self.tau
     }
-    fn get_v_reset(&self) -> f64 {
+    fn get_v_reset(&self) -> F {
         self.v_reset
     }
-    fn get_v_rest(&self) -> f64 {
+    fn get_v_rest(&self) -> F {
         self.v_rest
     }
     fn get_ts(&self) -> u64 {
         self.ts
     }
-    fn get_v_mem(&self) -> f64 {
+    fn get_v_mem(&self) -> F {
         self.v_mem
     }
-    fn set_v_mem(&mut self, val: f64) {
+    fn set_v_mem(&mut self, val: F) {
         self.v_mem = val;
     }
-    fn set_tau(&mut self, val: f64) {
+    fn set_tau(&mut self, val: F) {
         self.tau = val;
     }
-    fn set_v_reset(&mut self, val: f64) {
+    fn set_v_reset(&mut self, val: F) {
         self.v_reset = val;
     }
-    fn set_v_rest(&mut self, val: f64) {
+    fn set_v_rest(&mut self, val: F) {
         self.v_rest = val;
     }
     /* This function updates the membrane potential of the neuron when it receives at least one spike */
     fn set_ts(&mut self, val: u64) {
         self.ts = val;
     }
-    fn get_dt(&self) -> f64 {
+    fn get_dt(&self) -> F {
         self.dt
     }
-    fn set_dt(&mut self, val: f64) {
+    fn set_dt(&mut self, val: F) {
         self.dt = val;
     }
 }
\ No newline at end of file
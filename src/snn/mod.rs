@@ -3,6 +3,27 @@ pub mod lif_neuron;
 pub mod layer;
 pub mod network;
 pub mod spike_event;
+pub mod spike_vec;
+pub mod spike_reader;
+pub mod spike_file;
+pub mod aer;
+pub mod pipeline;
+pub mod probe;
+pub mod recording;
+pub mod vcd;
+pub mod svg;
+pub mod results;
+pub mod checkpoint;
 pub mod builder;
 pub mod failure;
-pub mod configuration;
\ No newline at end of file
+pub mod configuration;
+pub mod campaign;
+pub mod tmr_neuron;
+pub mod range_guard;
+pub mod protection;
+pub mod fixed_point;
+pub mod float;
+pub mod evaluation;
+pub mod quantization;
+pub mod fixed_lif_neuron;
+pub mod sparse;
\ No newline at end of file
@@ -1,6 +1,10 @@
 use crate::neuron::Neuron;
 use crate::snn::layer::Layer;
-use crate::spike_event::SpikeEvent;
+use crate::spike_event::{SpikeEvent, SpikeMessage};
+use crate::spike_vec::SpikeVec;
+use crate::snn::aer::{decode_aer, encode_aer, AerEvent};
+use crate::snn::probe::Probe;
+use crate::snn::recording::SpikeRecord;
 use crate::configuration::Configuration;
 use std::slice::IterMut;
 use std::sync::{Arc, Mutex};
@@ -8,23 +12,35 @@ use std::sync::mpsc::channel;
 use std::thread;
 use std::thread::JoinHandle;
 
+/** Way the layers of the network are run */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Executor {
+    Threaded,   /* one thread for each layer, connected by channels */
+    Lockstep,   /* all the layers instant by instant on the calling thread (same results as Threaded) */
+}
+
 #[derive(Debug, Clone)]
 pub struct SNN<N: Neuron + Clone + 'static, R: Configuration + Clone + Send + 'static> {
     layers: Vec<Arc<Mutex<Layer<N, R>>>>,
+    executor: Executor,
+    instant: u64,       /* next time instant of the step by step simulation */
 }
 
 impl<N: Neuron + Clone, R: Configuration + Clone + Send + 'static> SNN<N, R> {
     pub fn new(layers: Vec<Arc<Mutex<Layer<N, R>>>>) -> Self {
-        Self { layers }
+        Self { layers, executor: Executor::Threaded, instant: 0 }
     }
 
+    pub fn get_executor(&self) -> Executor { self.executor }
+    pub fn set_executor(&mut self, executor: Executor) { self.executor = executor }
+
     pub fn get_number_layers(&self) -> usize {
         self.layers.len()
     }
 
     pub fn get_input_layer_one_dim(&self) -> usize {
         let first_layer = self.layers[0].lock().unwrap();
-        first_layer.get_weights().first().unwrap().len()
+        first_layer.get_input_dimension()
     }
 
     pub fn get_output_last_layer_dim(&self) -> usize {
@@ -36,6 +52,16 @@ impl<N: Neuron + Clone, R: Configuration + Clone + Send + 'static> SNN<N, R> {
         self.layers.iter().map(|layer| layer.lock().unwrap().clone()).collect()
     }
 
+    /**
+    It checks whether the fault configured in at least one layer can change the output of the network.
+    If not, the simulation of the faulty network is useless since it behaves as the fault-free one.
+    The check is static and conservative (see `Layer::is_fault_effective`, `duration` is the number of
+    instants of the samples): false means provably masked, true only that the fault is not pruned.
+     */
+    pub fn is_fault_effective(&self, duration: u64) -> bool {
+        self.layers.iter().any(|layer| layer.lock().unwrap().is_fault_effective(duration))
+    }
+
     /**
     Process input spikes through the Spiking Neural Network and generate corresponding output spikes.
     The 'spikes' variable comprises an array for each neuron in the input layer, with each array
@@ -44,6 +70,211 @@ impl<N: Neuron + Clone, R: Configuration + Clone + Send + 'static> SNN<N, R> {
     This approach examines user input during the runtime.
      */
     pub fn process(&mut self, spikes: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+        self.process_with(spikes, self.executor)
+    }
+
+    /**
+    It advances the network by one time instant, on the calling thread, with the input spikes of that instant
+    (a 0/1 for each input neuron) and returns the output spikes of the last layer.
+    The state of the layers is kept between consecutive steps, so it can be inspected (e.g. with `get_layers`);
+    `reset` brings the network back to the instant 0. The step of the instant 0 starts from the reset state
+    (as `process` does), even if `reset` was not called.
+     */
+    pub fn step(&mut self, input_spikes: &[u8]) -> Vec<u8> {
+        let input_layer_dimension = self.get_input_layer_one_dim();
+        let output_layer_dimension = self.get_output_last_layer_dim();
+        if input_spikes.len() != input_layer_dimension {
+            panic!("Error: number of input spikes is not coherent with the input layer dimension");
+        }
+        if let Some(index) = input_spikes.iter().position(|spike| *spike != 0 && *spike != 1) {
+            panic!("Error: input spike must be 0 or 1 at for N={} at t={}", index, self.instant);
+        }
+
+        if self.instant == 0 {
+            self.reset();
+        }
+        let spike_event = SpikeEvent::new(self.instant, input_spikes.to_vec());
+        self.instant += 1;
+
+        /* an instant without input spikes is skipped, as in `process` */
+        let output_spike_event = if spike_event.is_empty() {
+            None
+        } else {
+            self.layers.iter()
+                .try_fold(spike_event, |event, layer| layer.lock().unwrap().process_event(&event))
+        };
+
+        output_spike_event.map_or(vec![0; output_layer_dimension], |event| event.get_spikes().to_vec())
+    }
+
+    /**
+    It resets the state of all the layers (as at the start of `process`) and the instant of `step`.
+     */
+    pub fn reset(&mut self) {
+        self.layers.iter().for_each(|layer| layer.lock().unwrap().init());
+        self.instant = 0;
+    }
+
+    /**
+    It attaches a probe to a neuron of a layer, recording its state at each instant of the next runs.
+     */
+    pub fn add_probe(&mut self, layer: usize, neuron: usize) {
+        let layer_ref = self.layers.get(layer).unwrap_or_else(|| panic!("The layer {} does not exist", layer));
+        layer_ref.lock().unwrap().add_probe(Probe::new(layer, neuron));
+    }
+
+    /** probes of all the layers, with the samples recorded since the last reset of the layers */
+    pub fn get_probes(&self) -> Vec<Probe<N::Float>> {
+        self.layers.iter().flat_map(|layer| layer.lock().unwrap().get_probes().to_vec()).collect()
+    }
+
+    /**
+    It processes the input spikes as `process` and returns, alongside the output, the time series
+    recorded by the probes.
+     */
+    pub fn process_probed(&mut self, spikes: &Vec<Vec<u8>>) -> (Vec<Vec<u8>>, Vec<Probe<N::Float>>) {
+        let output = self.process(spikes);
+        (output, self.get_probes())
+    }
+
+    /**
+    It enables the recording of the output spikes of every layer (hidden ones included).
+     */
+    pub fn enable_spike_recording(&mut self) {
+        self.layers.iter().for_each(|layer| layer.lock().unwrap().enable_spike_recording());
+    }
+
+    /** spikes recorded in each layer since the last reset of the layers (empty if the recording is disabled) */
+    pub fn get_spike_records(&self) -> Vec<SpikeRecord> {
+        self.layers.iter()
+            .map(|layer| {
+                let layer = layer.lock().unwrap();
+                layer.get_spike_record().cloned().unwrap_or_else(|| SpikeRecord::new(layer.get_number_neurons()))
+            })
+            .collect()
+    }
+
+    /** next time instant of `step` */
+    pub fn get_instant(&self) -> u64 { self.instant }
+
+    /**
+    It processes the input spikes as `process`, running the layers with the given executor
+    instead of the one of the network.
+     */
+    pub fn process_with(&mut self, spikes: &[Vec<u8>], executor: Executor) -> Vec<Vec<u8>> {
+        let packed_spikes = SNN::<N, R>::pack_spikes(spikes);
+
+        SNN::<N, R>::unpack_spikes(self.process_packed_with(&packed_spikes, executor))
+    }
+
+    /**
+    It processes a batch of samples (each one as the input of `process`) and returns the output of each one.
+    The layer threads are spawned once for the whole batch, and the state of the layers is reset
+    between consecutive samples (as `Layer::init` does at the start of `process`).
+     */
+    pub fn process_batch(&mut self, samples: &[Vec<Vec<u8>>]) -> Vec<Vec<Vec<u8>>> {
+        let input_layer_dimension = self.get_input_layer_one_dim();
+        let output_layer_dimension = self.get_output_last_layer_dim();
+
+        /* encode all the samples before spawning the threads */
+        let mut durations = Vec::with_capacity(samples.len());
+        let mut samples_events = Vec::with_capacity(samples.len());
+        for spikes in samples {
+            let packed_spikes = SNN::<N, R>::pack_spikes(spikes);
+            let spikes_duration = self.spikes_duration(&packed_spikes);
+            durations.push(spikes_duration);
+            samples_events.push(SNN::<N, R>::encode_spikes(input_layer_dimension, &packed_spikes, spikes_duration));
+        }
+
+        let output_events = match self.executor {
+            Executor::Threaded => self.process_events_batch(samples_events),
+            Executor::Lockstep => samples_events.into_iter().map(|events| self.process_events_lockstep(events)).collect(),
+        };
+
+        output_events
+            .into_iter()
+            .zip(durations)
+            .map(|(events, spikes_duration)| {
+                SNN::<N, R>::unpack_spikes(SNN::<N, R>::decode_spikes(output_layer_dimension, events, spikes_duration))
+            })
+            .collect()
+    }
+
+    /**
+    It processes a batch of samples as `process_batch`, splitting it in `num_networks` contiguous chunks
+    run in parallel, each one on a copy of the network. The network itself is left untouched.
+     */
+    pub fn process_batch_parallel(&self, samples: &[Vec<Vec<u8>>], num_networks: usize) -> Vec<Vec<Vec<u8>>> {
+        if num_networks == 0 {
+            panic!("The number of networks must be greater than 0");
+        }
+        if samples.is_empty() {
+            return Vec::new();
+        }
+        let chunk_size = samples.len().div_ceil(num_networks);
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = samples.chunks(chunk_size)
+                .map(|chunk| {
+                    let mut network = self.deep_clone();
+                    scope.spawn(move || network.process_batch(chunk))
+                })
+                .collect();
+
+            handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+        })
+    }
+
+    /* copy of the network with its own layers (cloning SNN shares them) */
+    fn deep_clone(&self) -> Self {
+        let mut network = SNN::new(self.get_layers().into_iter().map(|layer| Arc::new(Mutex::new(layer))).collect());
+        network.set_executor(self.executor);
+        network
+    }
+
+    /**
+    This function checks that the input spikes are 0 or 1 and packs the spikes of each neuron.
+     */
+    pub(crate) fn pack_spikes(spikes: &[Vec<u8>]) -> Vec<SpikeVec> {
+        /* check for 0 or 1 only */
+        for (in_neuron_index, neuron_spikes) in spikes.iter().enumerate() {
+            if let Some(t) = neuron_spikes.iter().position(|spike| *spike != 0 && *spike != 1) {
+                panic!("Error: input spike must be 0 or 1 at for N={} at t={}", in_neuron_index, t);
+            }
+        }
+        spikes.iter().map(|neuron_spikes| SpikeVec::from(neuron_spikes.as_slice())).collect()
+    }
+
+    pub(crate) fn unpack_spikes(spikes: Vec<SpikeVec>) -> Vec<Vec<u8>> {
+        spikes.iter().map(|neuron_spikes| neuron_spikes.to_vec()).collect()
+    }
+
+    /**
+    It processes the input spikes as `process`, with the spikes of each neuron packed in a SpikeVec
+    (one bit for each time instant), and returns the output spikes in the same form.
+     */
+    pub fn process_packed(&mut self, spikes: &[SpikeVec]) -> Vec<SpikeVec> {
+        self.process_packed_with(spikes, self.executor)
+    }
+
+    /**
+    It processes the input spikes given as AER events (timestamp and address of the input neuron, in any order)
+    and returns the output spikes of the last layer as AER events, ordered by timestamp and address.
+    Only the instants with at least one input spike are processed, as in `process`, so sparse event data
+    (e.g. from a DVS sensor) is fed without expanding it into a [neuron][instant] matrix.
+     */
+    pub fn process_aer(&mut self, events: &[AerEvent]) -> Vec<AerEvent> {
+        let input_spike_events = encode_aer(self.get_input_layer_one_dim(), events);
+
+        let output_spike_events = match self.executor {
+            Executor::Threaded => self.process_events(input_spike_events),
+            Executor::Lockstep => self.process_events_lockstep(input_spike_events),
+        };
+
+        decode_aer(&output_spike_events)
+    }
+
+    fn process_packed_with(&mut self, spikes: &[SpikeVec], executor: Executor) -> Vec<SpikeVec> {
         /* check and compute the spikes duration */
         let spikes_duration = self.spikes_duration(spikes);
 
@@ -55,7 +286,10 @@ impl<N: Neuron + Clone, R: Configuration + Clone + Send + 'static> SNN<N, R> {
             SNN::<N, R>::encode_spikes(input_layer_dimension, spikes, spikes_duration);
 
         /* process input */
-        let output_spike_events = self.process_events(input_spike_events);
+        let output_spike_events = match executor {
+            Executor::Threaded => self.process_events(input_spike_events),
+            Executor::Lockstep => self.process_events_lockstep(input_spike_events),
+        };
 
         /* decode output into array shape */
         SNN::<N, R>::decode_spikes(output_layer_dimension, output_spike_events, spikes_duration)
@@ -65,11 +299,9 @@ impl<N: Neuron + Clone, R: Configuration + Clone + Send + 'static> SNN<N, R> {
     This function checks if each vector passed in 'spikes' has the same number of spikes.
     If yes, it returns the duration, otherwise it triggers an error
      */
-    fn spikes_duration(&self, spikes: &Vec<Vec<u8>>) -> usize {
+    pub(crate) fn spikes_duration(&self, spikes: &[SpikeVec]) -> usize {
         /* compute length of the first Vec (0 if it does not exist) */
-        let spikes_duration = spikes.get(0)
-            .unwrap_or(&Vec::new())
-            .len();
+        let spikes_duration = spikes.first().map_or(0, |neuron_spikes| neuron_spikes.len());
 
         for neuron_spikes in spikes {
             if neuron_spikes.len() != spikes_duration {
@@ -82,21 +314,17 @@ impl<N: Neuron + Clone, R: Configuration + Clone + Send + 'static> SNN<N, R> {
     /**
     This function encodes the received input spikes in a Vec of **SpikeEvent** to process them.
      */
-    fn encode_spikes(input_layer_dimension: usize, spikes: &Vec<Vec<u8>>, spikes_duration: usize) -> Vec<SpikeEvent> {
+    pub(crate) fn encode_spikes(input_layer_dimension: usize, spikes: &[SpikeVec], spikes_duration: usize) -> Vec<SpikeEvent> {
         let mut spike_events = Vec::<SpikeEvent>::new();
         if spikes.len() != input_layer_dimension {
             panic!("Error: number of input spikes is not coherent with the input layer dimension, \
                     'spikes' must have a Vec for each neuron");
         }
         for t in 0..spikes_duration {
-            let mut t_spikes = Vec::<u8>::new();
+            let mut t_spikes = SpikeVec::new(input_layer_dimension);
             /* retrieve the input spikes for each neuron */
             for (in_neuron_index, spike) in spikes.iter().enumerate() {
-                /* check for 0 or 1 only */
-                if spike[t] != 0 && spike[t] != 1 {
-                    panic!("Error: input spike must be 0 or 1 at for N={} at t={}", in_neuron_index, t);
-                }
-                t_spikes.push(spike[t]);
+                t_spikes.set(in_neuron_index, spike.get(t));
             }
             let t_spike_event = SpikeEvent::new(t as u64, t_spikes);
             spike_events.push(t_spike_event);
@@ -105,18 +333,36 @@ impl<N: Neuron + Clone, R: Configuration + Clone + Send + 'static> SNN<N, R> {
     }
 
     /**
-    This function decodes a Vec of SpikeEvents and returns an output spikes matrix of 0/1
+    This function decodes a Vec of SpikeEvents and returns the packed output spikes of each neuron
      */
-    fn decode_spikes(output_layer_dimension: usize, spikes: Vec<SpikeEvent>, spikes_duration: usize) -> Vec<Vec<u8>> {
-        let mut raw_spikes = vec![vec![0; spikes_duration]; output_layer_dimension];
+    pub(crate) fn decode_spikes(output_layer_dimension: usize, spikes: Vec<SpikeEvent>, spikes_duration: usize) -> Vec<SpikeVec> {
+        let mut raw_spikes = vec![SpikeVec::new(spikes_duration); output_layer_dimension];
         for spike_event in spikes {
-            for (out_neuron_index, spike) in spike_event.get_spikes().into_iter().enumerate() {
-                raw_spikes[out_neuron_index][spike_event.get_ts() as usize] = spike;
+            for out_neuron_index in spike_event.get_active() {
+                raw_spikes[out_neuron_index].set(spike_event.get_ts() as usize, 1);
             }
         }
         raw_spikes
     }
 
+    /**
+    It processes the SpikeEvents on the calling thread: at each instant the event goes through the layers
+    one after the other, stopping at the first layer which does not fire (as with the threaded layers,
+    which send nothing to the next layer in that case).
+     */
+    fn process_events_lockstep(&mut self, spikes: Vec<SpikeEvent>) -> Vec<SpikeEvent> {
+        let mut layers: Vec<_> = self.layers.iter().map(|layer| layer.lock().unwrap()).collect();
+
+        /* initialize data structures, as each layer does at the start of its thread */
+        layers.iter_mut().for_each(|layer| layer.init());
+
+        /* (process only *effective* spike events) */
+        spikes.into_iter()
+            .filter(|spike_event| !spike_event.is_empty())
+            .filter_map(|spike_event| layers.iter_mut().try_fold(spike_event, |event, layer| layer.process_event(&event)))
+            .collect()
+    }
+
     fn process_events(&mut self, spikes: Vec<SpikeEvent>) -> Vec<SpikeEvent> {
         let mut threads = Vec::<JoinHandle<()>>::new();
 
@@ -150,7 +396,7 @@ impl<N: Neuron + Clone, R: Configuration + Clone + Send + 'static> SNN<N, R> {
         /* fire input SpikeEvents into *net_input_tx* */
         for spike_event in spikes {
             /* * check if there is at least 1 spike, otherwise skip to the next instant * */
-            if spike_event.get_spikes().iter().all(|spike| *spike == 0u8) {
+            if spike_event.is_empty() {
                 continue;   /* (process only *effective* spike events) */
             }
 
@@ -176,6 +422,67 @@ impl<N: Neuron + Clone, R: Configuration + Clone + Send + 'static> SNN<N, R> {
 
         output_events
     }
+
+    /**
+    It processes the SpikeEvents of each sample through the same layer threads,
+    separating consecutive samples with a `SpikeMessage::Reset` marker.
+     */
+    fn process_events_batch(&mut self, samples: Vec<Vec<SpikeEvent>>) -> Vec<Vec<SpikeEvent>> {
+        if samples.is_empty() {
+            return Vec::new();
+        }
+        let num_samples = samples.len();
+        let mut threads = Vec::<JoinHandle<()>>::new();
+
+        /* create channel to feed the (first layer of the) network */
+        let (net_input_tx, mut layer_rc) = channel::<SpikeMessage>();
+
+        /* create input TX and output RC for each layer and spawn layers' threads */
+        for layer_ref in self {
+            let (layer_tx, next_layer_rc) = channel::<SpikeMessage>();
+            let layer_ref_cloned = layer_ref.clone();
+
+            threads.push(thread::spawn(move || {
+                layer_ref_cloned.lock().unwrap().process_stream(layer_rc, layer_tx);
+            }));
+
+            layer_rc = next_layer_rc;
+        }
+        let net_output_rc = layer_rc;
+
+        /* fire the samples into *net_input_tx*, separated by the reset markers */
+        for (index, spikes) in samples.into_iter().enumerate() {
+            if index > 0 {
+                net_input_tx.send(SpikeMessage::Reset)
+                    .unwrap_or_else(|_| panic!("Unexpected error sending the reset of sample {}", index));
+            }
+            /* (process only *effective* spike events) */
+            for spike_event in spikes.into_iter().filter(|spike_event| !spike_event.is_empty()) {
+                let instant = spike_event.get_ts();
+                net_input_tx.send(SpikeMessage::Event(spike_event))
+                    .unwrap_or_else(|_| panic!("Unexpected error sending input spike event t={}", instant))
+            }
+        }
+
+        drop(net_input_tx); /* drop input tx, to make all the threads terminate */
+
+        /* get output SpikeEvents of each sample from *net_output* rc */
+        let mut output_events = Vec::with_capacity(num_samples);
+        output_events.push(Vec::<SpikeEvent>::new());
+        while let Ok(message) = net_output_rc.recv() {
+            match message {
+                SpikeMessage::Event(spike_event) => output_events.last_mut().unwrap().push(spike_event),
+                SpikeMessage::Reset => output_events.push(Vec::new()),
+            }
+        }
+
+        /* waiting for threads to terminate */
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        output_events
+    }
 }
 
 impl<'a, N: Neuron + Clone + 'static, R: Configuration + Clone + Send + 'static> IntoIterator for &'a mut SNN<N, R> {
@@ -1,28 +1,39 @@
 /** Neuron module */
+use crate::snn::float::Float;
 
 /** Trait for the implementation of any Neuron models */
 pub trait Neuron: Send {
+    /** floating point type of the parameters (f32 or f64) */
+    type Float: Float;
+
     /** The neuron function is invoked when incoming spikes from the previous layer.
         t: the time instant when input spikes are received
         extra_sum: the product of input spikes and incoming weights, always > 0
         intra_sum: the product of input spikes from the previous instant and the intra-layer weights
      */
-    fn get_v_th(&self) -> f64;
-    fn set_v_th(&mut self, new_val:f64);
-    fn calculate_v_mem(&mut self, t: u64, extra_sum: f64) -> u8;
+    fn get_v_th(&self) -> Self::Float;
+    fn set_v_th(&mut self, new_val: Self::Float);
+    fn calculate_v_mem(&mut self, t: u64, extra_sum: Self::Float) -> u8;
 
     /** initialize all data structures of Neuron */
     fn init(&mut self);
-    fn get_tau(&self) -> f64;
-    fn get_v_reset(&self) -> f64;
-    fn get_v_rest(&self) -> f64;
+    fn get_tau(&self) -> Self::Float;
+    fn get_v_reset(&self) -> Self::Float;
+    fn get_v_rest(&self) -> Self::Float;
     fn get_ts(&self)->u64;
-    fn get_v_mem(&self)->f64;
-    fn set_v_mem(&mut self, val: f64);
-    fn set_tau(&mut self, val: f64);
-    fn set_v_reset(&mut self, val: f64);
-    fn set_v_rest(&mut self, val: f64);
+    fn get_v_mem(&self) -> Self::Float;
+    fn set_v_mem(&mut self, val: Self::Float);
+    fn set_tau(&mut self, val: Self::Float);
+    fn set_v_reset(&mut self, val: Self::Float);
+    fn set_v_rest(&mut self, val: Self::Float);
     fn set_ts(&mut self, val: u64);
-    fn get_dt(&self)->f64;
-    fn set_dt(&mut self,val:f64);
-}
+    fn get_dt(&self) -> Self::Float;
+    fn set_dt(&mut self, val: Self::Float);
+
+    /** width (in bits) of the words storing the parameters, where the faults are injected */
+    fn get_word_width(&self) -> usize { Self::Float::WIDTH }
+    /** conversion of a parameter into the word storing it */
+    fn encode_word(&self, val: Self::Float) -> u64 { val.to_word() }
+    /** conversion of a word into the parameter it stores */
+    fn decode_word(&self, word: u64) -> Self::Float { Self::Float::from_word(word) }
+}
\ No newline at end of file
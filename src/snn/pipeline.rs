@@ -0,0 +1,156 @@
+/** Pipeline module */
+use std::collections::VecDeque;
+use std::panic;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::thread::JoinHandle;
+use crate::configuration::Configuration;
+use crate::neuron::Neuron;
+use crate::snn::network::SNN;
+use crate::spike_event::{SpikeEvent, SpikeMessage};
+
+/**
+Long-lived execution of a network: the layer threads are spawned once and stay alive, consuming a stream
+of samples each one followed by a `SpikeMessage::Reset` marker. Since every layer moves to the next sample
+as soon as it receives the marker, layer k processes sample i while layer k+1 is still processing sample i-1.
+ */
+pub struct Pipeline<N: Neuron + Clone + 'static, R: Configuration + Clone + Send + 'static> {
+    snn: SNN<N, R>,
+    input_dimension: usize,
+    output_dimension: usize,
+    input_tx: Option<Sender<SpikeMessage>>,     /* None when the pipeline is stopped */
+    output_rc: Receiver<SpikeMessage>,
+    threads: Vec<JoinHandle<()>>,
+    pending: VecDeque<usize>,                   /* durations of the samples submitted and not received yet */
+}
+
+impl<N: Neuron + Clone + 'static, R: Configuration + Clone + Send + 'static> Pipeline<N, R> {
+    /**
+    It spawns a thread for each layer of the network. The network is given back by `finish`.
+     */
+    pub fn new(mut snn: SNN<N, R>) -> Self {
+        let input_dimension = snn.get_input_layer_one_dim();
+        let output_dimension = snn.get_output_last_layer_dim();
+        let mut threads = Vec::<JoinHandle<()>>::new();
+
+        /* create channel to feed the (first layer of the) network */
+        let (input_tx, mut layer_rc) = channel::<SpikeMessage>();
+
+        /* create input TX and output RC for each layer and spawn layers' threads */
+        for layer_ref in &mut snn {
+            let (layer_tx, next_layer_rc) = channel::<SpikeMessage>();
+            let layer_ref_cloned = layer_ref.clone();
+
+            /* the layer stays locked by its thread until the pipeline is stopped */
+            threads.push(thread::spawn(move || {
+                layer_ref_cloned.lock().unwrap().process_stream(layer_rc, layer_tx);
+            }));
+
+            layer_rc = next_layer_rc;
+        }
+
+        Self {
+            snn,
+            input_dimension,
+            output_dimension,
+            input_tx: Some(input_tx),
+            output_rc: layer_rc,
+            threads,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /** number of samples submitted whose output has not been received yet */
+    pub fn get_pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /**
+    It feeds a sample (as the input of `SNN::process`) into the pipeline without waiting for its output.
+     */
+    pub fn submit(&mut self, spikes: &[Vec<u8>]) {
+        let packed_spikes = SNN::<N, R>::pack_spikes(spikes);
+        let spikes_duration = self.snn.spikes_duration(&packed_spikes);
+        let spike_events = SNN::<N, R>::encode_spikes(self.input_dimension, &packed_spikes, spikes_duration);
+
+        let input_tx = self.input_tx.as_ref().unwrap();
+        /* (process only *effective* spike events) */
+        for spike_event in spike_events.into_iter().filter(|spike_event| !spike_event.is_empty()) {
+            let instant = spike_event.get_ts();
+            input_tx.send(SpikeMessage::Event(spike_event))
+                .unwrap_or_else(|_| panic!("Unexpected error sending input spike event t={}", instant))
+        }
+        input_tx.send(SpikeMessage::Reset)
+            .unwrap_or_else(|_| panic!("Unexpected error sending the end of the sample"));
+
+        self.pending.push_back(spikes_duration);
+    }
+
+    /**
+    It waits for the output of the oldest sample submitted, None if there are no pending samples.
+     */
+    pub fn receive(&mut self) -> Option<Vec<Vec<u8>>> {
+        let spikes_duration = self.pending.pop_front()?;
+
+        let mut output_events = Vec::<SpikeEvent>::new();
+        loop {
+            match self.output_rc.recv() {
+                Ok(SpikeMessage::Event(spike_event)) => output_events.push(spike_event),
+                Ok(SpikeMessage::Reset) => break,
+                Err(_) => panic!("Unexpected error receiving the output of the network"),
+            }
+        }
+
+        let output_spikes = SNN::<N, R>::decode_spikes(self.output_dimension, output_events, spikes_duration);
+        Some(SNN::<N, R>::unpack_spikes(output_spikes))
+    }
+
+    /**
+    It submits all the samples and returns their outputs, in the same order.
+    - If there are pending samples (submitted and not received), the process panics
+     */
+    pub fn process(&mut self, samples: &[Vec<Vec<u8>>]) -> Vec<Vec<Vec<u8>>> {
+        if !self.pending.is_empty() {
+            panic!("The outputs of the samples already submitted must be received first");
+        }
+        samples.iter().for_each(|spikes| self.submit(spikes));
+
+        (0..samples.len()).filter_map(|_| self.receive()).collect()
+    }
+
+    /**
+    It stops the layer threads, discarding the outputs not received yet, and gives back the network.
+    - If a layer thread panicked, its panic is propagated
+     */
+    pub fn finish(mut self) -> SNN<N, R> {
+        if let Err(panic) = self.stop() {
+            panic::resume_unwind(panic);
+        }
+        self.snn.clone()
+    }
+
+    /* it stops the threads and returns the panic of the first layer thread which panicked, if any */
+    fn stop(&mut self) -> thread::Result<()> {
+        /* drop input tx, to make all the threads terminate */
+        self.input_tx = None;
+        /* discard the outputs not received, until the last layer closes its channel */
+        while self.output_rc.recv().is_ok() {}
+
+        let mut result = Ok(());
+        for thread in self.threads.drain(..) {
+            let joined = thread.join();
+            if result.is_ok() {
+                result = joined;
+            }
+        }
+        self.pending.clear();
+        result
+    }
+}
+
+impl<N: Neuron + Clone + 'static, R: Configuration + Clone + Send + 'static> Drop for Pipeline<N, R> {
+    fn drop(&mut self) {
+        /* (a panic here while unwinding would abort the process) */
+        let _ = self.stop();
+    }
+}
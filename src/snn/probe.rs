@@ -0,0 +1,49 @@
+/** Probe module */
+use crate::snn::float::Float;
+
+/** State of a neuron recorded by a Probe at the end of an instant */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeSample<F: Float = f64> {
+    pub instant: u64,
+    pub v_mem: F,       /* membrane potential after the update (and the reset, if the neuron fired) */
+    pub ts: u64,        /* last instant in which the neuron received at least one spike */
+    pub v_th: F,        /* threshold potential */
+    pub extra_sum: F,   /* weighted sum of the input spikes */
+    pub intra_sum: F,   /* weighted sum of the spikes of the layer in the previous instant */
+    pub spike: u8,      /* output spike */
+}
+
+/**
+Probe attached to a neuron of a layer: it records the state of the neuron at each instant
+processed by the layer (the instants in which the layer receives no spikes are skipped, as in the simulation).
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Probe<F: Float = f64> {
+    layer: usize,
+    neuron: usize,
+    samples: Vec<ProbeSample<F>>,
+}
+
+impl<F: Float> Probe<F> {
+    pub fn new(layer: usize, neuron: usize) -> Self {
+        Self { layer, neuron, samples: Vec::new() }
+    }
+
+    pub fn get_layer(&self) -> usize { self.layer }
+    pub fn get_neuron(&self) -> usize { self.neuron }
+    pub fn get_samples(&self) -> &[ProbeSample<F>] { &self.samples }
+
+    /** time series of the membrane potential, as (instant, v_mem) pairs */
+    pub fn get_v_mem(&self) -> Vec<(u64, F)> {
+        self.samples.iter().map(|sample| (sample.instant, sample.v_mem)).collect()
+    }
+
+    pub fn record(&mut self, sample: ProbeSample<F>) {
+        self.samples.push(sample);
+    }
+
+    /** remove the recorded samples */
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
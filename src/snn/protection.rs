@@ -0,0 +1,124 @@
+/** Protection module */
+/** Error detection/correction code protecting each weight word */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeightProtection {
+    Parity, /* one parity bit per word: detects odd errors, the weight is zeroed */
+    SecDed, /* extended Hamming (72, 64): corrects single errors, detects double ones (the weight is zeroed) */
+}
+
+/**
+Simulated protected storage of a weights matrix.
+The check bits of each word are computed once from the fault-free weights and are assumed
+fault-free; every faulty word read from the matrix goes through the decoder.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtectedWeights {
+    protection: WeightProtection,
+    check_bits: Vec<Vec<u8>>,   /* bit 7 = parity of the word, bits 0..6 = Hamming check bits (SecDed only) */
+    detected: u64,              /* number of words with a detected uncorrectable error (zeroed) */
+    corrected: u64,             /* number of words corrected to the original value */
+    undetected: u64,            /* number of words read with an error not seen (or wrongly corrected) */
+}
+
+impl ProtectedWeights {
+    pub fn new(protection: WeightProtection, words: &[Vec<u64>]) -> Self {
+        let check_bits = words.iter()
+            .map(|row| row.iter().map(|word| encode(protection, *word)).collect())
+            .collect();
+        Self { protection, check_bits, detected: 0, corrected: 0, undetected: 0 }
+    }
+
+    pub fn get_protection(&self) -> WeightProtection { self.protection }
+    pub fn get_detected(&self) -> u64 { self.detected }
+    pub fn get_corrected(&self) -> u64 { self.corrected }
+    pub fn get_undetected(&self) -> u64 { self.undetected }
+
+    /** reset the counters of detected, corrected and undetected errors */
+    pub fn init(&mut self) {
+        self.detected = 0;
+        self.corrected = 0;
+        self.undetected = 0;
+    }
+
+    /**
+    It reads the word (i, j) of the matrix through the decoder, after it has been changed from `old` to `word`.
+    It returns the word actually seen by the layer: the corrected one, `word` if the error is not detected,
+    None if the error is detected but not correctable (the layer zeroes the weight).
+     */
+    pub fn read(&mut self, i: usize, j: usize, old: u64, word: u64) -> Option<u64> {
+        let stored = match self.check_bits.get(i).and_then(|row| row.get(j)) {
+            Some(stored) => *stored,
+            None => return Some(word),
+        };
+
+        match decode(self.protection, stored, word) {
+            Some(read) => {
+                if read != old {
+                    self.undetected += 1;
+                } else if word != old {
+                    self.corrected += 1;
+                }
+                Some(read)
+            }
+            None => {
+                self.detected += 1;
+                None
+            }
+        }
+    }
+}
+
+/* position in the Hamming codeword of each data bit (positions which are not powers of two) */
+const DATA_POSITIONS: [u8; 64] = data_positions();
+
+const fn data_positions() -> [u8; 64] {
+    let mut positions = [0u8; 64];
+    let mut position = 3u8;
+    let mut k = 0;
+    while k < 64 {
+        if position & (position - 1) != 0 {
+            positions[k] = position;
+            k += 1;
+        }
+        position += 1;
+    }
+    positions
+}
+
+/* check bits of a word: the XOR of the positions of the bits set (SecDed) and the parity */
+fn encode(protection: WeightProtection, word: u64) -> u8 {
+    let parity = ((word.count_ones() % 2) as u8) << 7;
+    match protection {
+        WeightProtection::Parity => parity,
+        WeightProtection::SecDed => {
+            let mut syndrome = 0u8;
+            let mut bits = word;
+            while bits != 0 {
+                syndrome ^= DATA_POSITIONS[bits.trailing_zeros() as usize];
+                bits &= bits - 1;
+            }
+            parity | syndrome
+        }
+    }
+}
+
+/* it returns the decoded word, None if the error is detected but not correctable */
+fn decode(protection: WeightProtection, stored: u8, word: u64) -> Option<u64> {
+    let diff = stored ^ encode(protection, word);
+    let odd = diff & 0x80 != 0;
+    match protection {
+        WeightProtection::Parity => if odd { None } else { Some(word) },
+        WeightProtection::SecDed => {
+            let syndrome = diff & 0x7f;
+            if !odd {
+                /* no error or double error */
+                return if syndrome == 0 { Some(word) } else { None };
+            }
+            /* single error: in the parity bit, in a check bit or in a data bit */
+            if syndrome == 0 || syndrome.is_power_of_two() {
+                return Some(word);
+            }
+            DATA_POSITIONS.iter().position(|position| *position == syndrome).map(|k| word ^ (1u64 << k))
+        }
+    }
+}
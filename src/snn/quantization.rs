@@ -0,0 +1,79 @@
+/** Quantization module */
+use crate::configuration::Configuration;
+use crate::neuron::Neuron;
+use crate::snn::builder::SnnBuilder;
+use crate::snn::evaluation::accuracy;
+use crate::snn::float::Float;
+
+/** Scale used to map the weights to the quantization levels */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuantizationScheme {
+    Uniform,    /* one scale for the whole matrix */
+    PerRow,     /* one scale for each row (the incoming weights of a neuron) */
+}
+
+/** Accuracy of the network with the weights quantized on `bits` bits */
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizationResult {
+    pub bits: usize,
+    pub accuracy: f64,  /* percentage of samples correctly classified */
+}
+
+/**
+It maps a matrix of weights to uniformly spaced levels on `bits` bits, keeping 0 exact.
+If all the weights have the same sign all the 2^bits levels are used, otherwise one bit is the sign.
+ */
+pub fn quantize_weights<F: Float>(weights: &[Vec<F>], bits: usize, scheme: QuantizationScheme) -> Vec<Vec<F>> {
+    if bits == 0 || bits > 32 {
+        panic!("The number of bits of the quantization must be in the range [1, 32]");
+    }
+    match scheme {
+        QuantizationScheme::Uniform => {
+            let step = compute_step(weights.iter().flatten(), bits);
+            weights.iter().map(|row| quantize_row(row, step)).collect()
+        }
+        QuantizationScheme::PerRow => {
+            weights.iter().map(|row| quantize_row(row, compute_step(row.iter(), bits))).collect()
+        }
+    }
+}
+
+/**
+It quantizes the network loaded in the builder for each number of bits of the range and evaluates it
+on the dataset, producing the table accuracy vs bits.
+ */
+pub fn quantization_sweep<N: Neuron + Clone + 'static, R: Configuration + Clone + Send + 'static>(
+    builder: &SnnBuilder<N, R>, bits: std::ops::RangeInclusive<usize>, scheme: QuantizationScheme,
+    inputs: &[Vec<Vec<u8>>], labels: &[u8], assignments: &[u8]) -> Vec<QuantizationResult> {
+    bits.map(|n| {
+        let mut snn = builder.clone().quantize_weights(n, scheme).build();
+        QuantizationResult { bits: n, accuracy: accuracy(&mut snn, inputs, labels, assignments) }
+    }).collect()
+}
+
+/**
+It formats the results of a sweep as a table with one row per number of bits.
+ */
+pub fn format_table(results: &[QuantizationResult]) -> String {
+    let mut table = String::from("bits,accuracy\n");
+    for result in results {
+        table.push_str(&format!("{},{:.2}\n", result.bits, result.accuracy));
+    }
+    table
+}
+
+/* distance between two consecutive levels, 0 if all the weights are 0 */
+fn compute_step<'a, F: Float>(weights: impl Iterator<Item = &'a F>, bits: usize) -> f64 {
+    let (min, max) = weights.map(|weight| weight.to_f64())
+        .fold((0f64, 0f64), |(min, max), weight| (min.min(weight), max.max(weight)));
+    let max_abs = max.max(-min);
+    let levels = if min < 0.0 && max > 0.0 { (1u64 << (bits - 1)) - 1 } else { (1u64 << bits) - 1 };
+    max_abs / levels.max(1) as f64
+}
+
+fn quantize_row<F: Float>(row: &[F], step: f64) -> Vec<F> {
+    if step == 0.0 {
+        return row.to_vec();
+    }
+    row.iter().map(|weight| F::from_f64((weight.to_f64() / step).round() * step)).collect()
+}
@@ -0,0 +1,160 @@
+/** Range guard module */
+use crate::neuron::Neuron;
+use crate::snn::float::Float;
+
+/** Action taken by the RangeGuard when a value is out of its valid range */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GuardAction {
+    Clamp,      /* the value is clamped into the range (NaN goes to the lower bound) */
+    Restore,    /* the value is restored to the golden one */
+}
+
+/**
+Protection of the neuron parameters and of the weights of a Layer.
+The valid ranges and the golden values are taken from the fault-free layer; after each injection of a fault
+the Layer checks the value hit by the fault and clamps or restores it if out of range
+(v_mem, ts and prev_spikes are not guarded).
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeGuard<F: Float = f64> {
+    action: GuardAction,
+    neurons: Vec<[F; 5]>,               /* golden (v_th, v_rest, v_reset, tau, dt) of each neuron */
+    weights: Vec<Vec<F>>,               /* golden weights */
+    intra_weights: Vec<Vec<F>>,         /* golden intra weights */
+    neuron_ranges: [(F, F); 5],         /* valid range of each neuron parameter */
+    weights_range: (F, F),              /* valid range of the weights */
+    intra_weights_range: (F, F),        /* valid range of the intra weights */
+    detected: u64,                      /* number of values found out of range */
+    corrected: u64,                     /* number of values brought back to the golden value */
+}
+
+impl<F: Float> RangeGuard<F> {
+    /**
+    It creates the guard from the golden neurons and weights of a layer.
+    Each range goes from the minimum to the maximum golden value, widened on both sides by
+    `margin` times the largest absolute value.
+     */
+    pub fn new<N: Neuron<Float = F>>(neurons: &[N], weights: &[Vec<F>], intra_weights: &[Vec<F>], margin: f64, action: GuardAction) -> Self {
+        let golden: Vec<[F; 5]> = neurons.iter().map(get_params).collect();
+
+        let mut neuron_ranges = [(F::ZERO, F::ZERO); 5];
+        for (p, range) in neuron_ranges.iter_mut().enumerate() {
+            *range = compute_range(golden.iter().map(|params| params[p]), margin);
+        }
+
+        Self {
+            action,
+            neuron_ranges,
+            weights_range: compute_range(weights.iter().flatten().copied(), margin),
+            intra_weights_range: compute_range(intra_weights.iter().flatten().copied(), margin),
+            neurons: golden,
+            weights: weights.to_vec(),
+            intra_weights: intra_weights.to_vec(),
+            detected: 0,
+            corrected: 0,
+        }
+    }
+
+    pub fn get_action(&self) -> GuardAction { self.action }
+    pub fn get_detected(&self) -> u64 { self.detected }
+    pub fn get_corrected(&self) -> u64 { self.corrected }
+    pub fn get_weights_range(&self) -> (F, F) { self.weights_range }
+    pub fn get_intra_weights_range(&self) -> (F, F) { self.intra_weights_range }
+
+    /** reset the counters of detected and corrected values */
+    pub fn init(&mut self) {
+        self.detected = 0;
+        self.corrected = 0;
+    }
+
+    /**
+    It checks the parameters of the neurons and the weights of a layer,
+    fixing every value found out of its valid range.
+     */
+    pub fn check<N: Neuron<Float = F>>(&mut self, neurons: &mut [N], weights: &mut [Vec<F>], intra_weights: &mut [Vec<F>]) {
+        for (index, neuron) in neurons.iter_mut().enumerate() {
+            self.check_neuron(index, neuron);
+        }
+        for i in 0..weights.len() {
+            for j in 0..weights[i].len() {
+                self.check_weight((i, j), weights);
+            }
+        }
+        for i in 0..intra_weights.len() {
+            for j in 0..intra_weights[i].len() {
+                self.check_intra_weight((i, j), intra_weights);
+            }
+        }
+    }
+
+    /** it checks the parameters of the neuron in position `index`, fixing the ones out of range */
+    pub fn check_neuron<N: Neuron<Float = F>>(&mut self, index: usize, neuron: &mut N) {
+        let mut params = get_params(neuron);
+        let mut changed = false;
+        for p in 0..params.len() {
+            let golden = self.neurons.get(index).map(|golden| golden[p]);
+            if let Some(val) = self.fix(params[p], self.neuron_ranges[p], golden) {
+                params[p] = val;
+                changed = true;
+            }
+        }
+        if changed {
+            neuron.set_v_th(params[0]);
+            neuron.set_v_rest(params[1]);
+            neuron.set_v_reset(params[2]);
+            neuron.set_tau(params[3]);
+            neuron.set_dt(params[4]);
+        }
+    }
+
+    /** it checks the weight in position (i, j), fixing it if out of range */
+    pub fn check_weight(&mut self, (i, j): (usize, usize), weights: &mut [Vec<F>]) {
+        let golden = self.weights.get(i).and_then(|row| row.get(j)).copied();
+        if let Some(weight) = weights.get_mut(i).and_then(|row| row.get_mut(j)) {
+            if let Some(val) = self.fix(*weight, self.weights_range, golden) {
+                *weight = val;
+            }
+        }
+    }
+
+    /** it checks the intra weight in position (i, j), fixing it if out of range */
+    pub fn check_intra_weight(&mut self, (i, j): (usize, usize), intra_weights: &mut [Vec<F>]) {
+        let golden = self.intra_weights.get(i).and_then(|row| row.get(j)).copied();
+        if let Some(weight) = intra_weights.get_mut(i).and_then(|row| row.get_mut(j)) {
+            if let Some(val) = self.fix(*weight, self.intra_weights_range, golden) {
+                *weight = val;
+            }
+        }
+    }
+
+    /* it returns the new value if val is out of range, None otherwise */
+    fn fix(&mut self, val: F, (min, max): (F, F), golden: Option<F>) -> Option<F> {
+        if val >= min && val <= max {
+            return None;
+        }
+        self.detected += 1;
+
+        let new_val = match (self.action, golden) {
+            (GuardAction::Restore, Some(golden)) => golden,
+            _ => if val.is_nan() || val < min { min } else { max },
+        };
+        if Some(new_val) == golden {
+            self.corrected += 1;
+        }
+        Some(new_val)
+    }
+}
+
+fn get_params<N: Neuron>(neuron: &N) -> [N::Float; 5] {
+    [neuron.get_v_th(), neuron.get_v_rest(), neuron.get_v_reset(), neuron.get_tau(), neuron.get_dt()]
+}
+
+fn compute_range<F: Float>(values: impl Iterator<Item = F>, margin: f64) -> (F, F) {
+    let (min, max) = values.map(|val| val.to_f64())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), val| (min.min(val), max.max(val)));
+    if min > max {
+        return (F::ZERO, F::ZERO);
+    }
+    let delta = margin.abs() * min.abs().max(max.abs());
+    (F::from_f64(min - delta), F::from_f64(max + delta))
+}
@@ -0,0 +1,90 @@
+/** Spike recording module */
+use crate::spike_event::SpikeEvent;
+
+/**
+Spikes fired by the neurons of a layer during a run, stored sparsely as (instant, neuron) pairs
+in order of time (and of neuron in the same instant).
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpikeRecord {
+    num_neurons: usize,
+    spikes: Vec<(u64, usize)>,
+}
+
+impl SpikeRecord {
+    pub fn new(num_neurons: usize) -> Self {
+        Self { num_neurons, spikes: Vec::new() }
+    }
+
+    /** record of the spikes of a run given as a matrix [neuron][instant] (as the output of `SNN::process`) */
+    pub fn from_spikes(spikes: &[Vec<u8>]) -> Self {
+        let duration = spikes.iter().map(|neuron_spikes| neuron_spikes.len()).max().unwrap_or(0);
+        let mut record = SpikeRecord::new(spikes.len());
+        for instant in 0..duration {
+            for (neuron, neuron_spikes) in spikes.iter().enumerate() {
+                if neuron_spikes.get(instant).is_some_and(|spike| *spike != 0) {
+                    record.spikes.push((instant as u64, neuron));
+                }
+            }
+        }
+        record
+    }
+
+    pub fn get_num_neurons(&self) -> usize { self.num_neurons }
+    pub fn get_spikes(&self) -> &[(u64, usize)] { &self.spikes }
+
+    /** it appends the spikes of an output SpikeEvent of the layer */
+    pub fn record(&mut self, spike_event: &SpikeEvent) {
+        let instant = spike_event.get_ts();
+        self.spikes.extend(spike_event.get_active().map(|neuron| (instant, neuron)));
+    }
+
+    pub fn clear(&mut self) {
+        self.spikes.clear();
+    }
+
+    /** number of spikes fired by each neuron */
+    pub fn get_spike_counts(&self) -> Vec<u64> {
+        let mut counts = vec![0; self.num_neurons];
+        for (_, neuron) in self.spikes.iter() {
+            counts[*neuron] += 1;
+        }
+        counts
+    }
+
+    /** spikes per instant fired by each neuron during a run of `duration` instants */
+    pub fn get_firing_rates(&self, duration: usize) -> Vec<f64> {
+        self.get_spike_counts().into_iter()
+            .map(|count| if duration == 0 { 0.0 } else { count as f64 / duration as f64 })
+            .collect()
+    }
+}
+
+/**
+It compares the spikes recorded in the layers of a golden and of a faulty run and returns
+the first spike which differs as (layer, instant, neuron): the earliest one, and the one of the first layer
+among the ones in the same instant. None if the runs fired the same spikes.
+ */
+pub fn first_difference(golden: &[SpikeRecord], faulty: &[SpikeRecord]) -> Option<(usize, u64, usize)> {
+    let empty = SpikeRecord::default();
+    let num_layers = golden.len().max(faulty.len());
+
+    (0..num_layers)
+        .filter_map(|layer| {
+            let golden_spikes = golden.get(layer).unwrap_or(&empty).get_spikes();
+            let faulty_spikes = faulty.get(layer).unwrap_or(&empty).get_spikes();
+            first_layer_difference(golden_spikes, faulty_spikes).map(|(instant, neuron)| (layer, instant, neuron))
+        })
+        .min_by_key(|(layer, instant, _)| (*instant, *layer))
+}
+
+/* first (instant, neuron) pair present in only one of the two sorted lists */
+fn first_layer_difference(golden: &[(u64, usize)], faulty: &[(u64, usize)]) -> Option<(u64, usize)> {
+    let common = golden.iter().zip(faulty.iter()).take_while(|(g, f)| g == f).count();
+    match (golden.get(common), faulty.get(common)) {
+        (Some(g), Some(f)) => Some(*g.min(f)),
+        (Some(g), None) => Some(*g),
+        (None, Some(f)) => Some(*f),
+        (None, None) => None,
+    }
+}
@@ -0,0 +1,328 @@
+/** Campaign results module */
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{Error, ErrorKind, Read, Result, Write};
+use std::path::Path;
+use std::time::Duration;
+use crate::configuration::Configuration;
+use crate::evaluation::classify;
+use crate::failure::{Components, Failure, StuckAt0, StuckAt1, TransientBitFlip};
+
+const CSV_HEADER: &str = "layer,component,neuron,row,col,bit,failure,outcomes,injection_times,accuracy,runtime_ms";
+
+/** Format of a results file */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultFormat {
+    Csv,    /* a "# spec" line and a header line, then a line of comma separated values for each fault */
+    Jsonl,  /* a {"spec": ...} object, then a JSON object for each fault, one per line */
+}
+
+impl ResultFormat {
+    /** format given by the extension of the file: `.jsonl` or `.json` for JSON Lines, CSV otherwise */
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|extension| extension.to_str()) {
+            Some("jsonl") | Some("json") => ResultFormat::Jsonl,
+            _ => ResultFormat::Csv,
+        }
+    }
+}
+
+/** Effect of a fault on the output of a sample, compared with the golden (fault free) run */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleOutcome {
+    Masked,         /* same output spikes as the golden run */
+    Changed,        /* different output spikes, same classification as the golden run */
+    Misclassified,  /* classification different from the one of the golden run */
+}
+
+impl SampleOutcome {
+    /**
+    It compares the output spikes ([neuron][instant]) of the faulty and of the golden run of a sample.
+    Without the assignments of the output neurons the outputs are not classified, so any difference is `Changed`.
+     */
+    pub fn from_outputs(golden: &[Vec<u8>], faulty: &[Vec<u8>], assignments: Option<&[u8]>) -> Self {
+        if golden == faulty {
+            return SampleOutcome::Masked;
+        }
+        match assignments {
+            Some(assignments) if classify(golden, assignments) != classify(faulty, assignments) => SampleOutcome::Misclassified,
+            _ => SampleOutcome::Changed,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SampleOutcome::Masked => "masked",
+            SampleOutcome::Changed => "changed",
+            SampleOutcome::Misclassified => "misclassified",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "masked" => Some(SampleOutcome::Masked),
+            "changed" => Some(SampleOutcome::Changed),
+            "misclassified" => Some(SampleOutcome::Misclassified),
+            _ => None,
+        }
+    }
+}
+
+/**
+Result of the simulation of a fault: where it was injected, how it changed the output of each sample
+of the campaign and how long the simulation took.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaultResult {
+    pub layer: usize,
+    pub component: Components,
+    pub neuron: usize,
+    pub row: Option<usize>,             /* (row, col) of the faulted weight, for Weights and IntraWeights */
+    pub col: Option<usize>,
+    pub failure: Failure,               /* fault model and bit position */
+    pub outcomes: Vec<SampleOutcome>,   /* one for each sample */
+    pub injection_times: Vec<Option<u64>>,  /* instant of the (first) injection of the fault in each sample, None if never injected */
+    pub accuracy: Option<f64>,          /* percentage of samples classified with their label */
+    pub runtime: Duration,              /* stored with a resolution of 1 ms */
+}
+
+impl FaultResult {
+    /**
+    It creates the result of the fault of a configuration injected in the given layer, with no outcomes yet.
+     */
+    pub fn new<R: Configuration>(layer: usize, configuration: &R) -> Self {
+        Self {
+            layer,
+            component: configuration.get_vec_components().first().cloned().unwrap_or(Components::None),
+            neuron: configuration.get_index_neuron(),
+            row: None,
+            col: None,
+            failure: configuration.get_failure(),
+            outcomes: Vec::new(),
+            injection_times: Vec::new(),
+            accuracy: None,
+            runtime: Duration::ZERO,
+        }
+    }
+
+    /** number of samples whose output was changed by the fault */
+    pub fn get_num_effective(&self) -> usize {
+        self.outcomes.iter().filter(|outcome| **outcome != SampleOutcome::Masked).count()
+    }
+
+    fn key(&self) -> FaultKey {
+        (self.layer, self.component.get_name(), self.neuron, self.failure.get_name(), self.failure.get_position())
+    }
+
+    fn to_csv(&self) -> String {
+        let outcomes: Vec<&str> = self.outcomes.iter().map(|outcome| outcome.as_str()).collect();
+        let injection_times: Vec<String> = self.injection_times.iter().map(|time| time.map_or("-".to_string(), |time| time.to_string())).collect();
+        format!("{},{},{},{},{},{},{},{},{},{},{}",
+                self.layer, self.component.get_name(), self.neuron, optional(self.row), optional(self.col),
+                optional(self.failure.get_position()), self.failure.get_name(), outcomes.join(";"),
+                injection_times.join(";"), optional(self.accuracy), self.runtime.as_millis())
+    }
+
+    fn to_json(&self) -> String {
+        let json = |value: String| if value.is_empty() { "null".to_string() } else { value };
+        let outcomes: Vec<String> = self.outcomes.iter().map(|outcome| format!("\"{}\"", outcome.as_str())).collect();
+        let injection_times: Vec<String> = self.injection_times.iter().map(|time| json(optional(*time))).collect();
+        format!("{{\"layer\":{},\"component\":\"{}\",\"neuron\":{},\"row\":{},\"col\":{},\"bit\":{},\"failure\":\"{}\",\"outcomes\":[{}],\"injection_times\":[{}],\"accuracy\":{},\"runtime_ms\":{}}}",
+                self.layer, self.component.get_name(), self.neuron, json(optional(self.row)), json(optional(self.col)),
+                json(optional(self.failure.get_position())), self.failure.get_name(), outcomes.join(","),
+                injection_times.join(","), json(optional(self.accuracy)), self.runtime.as_millis())
+    }
+
+    /* it builds the result from the text of each field (empty for the missing values, items of the lists separated by ';',
+    with '-' or null for the missing ones) */
+    fn from_fields(fields: &HashMap<String, String>) -> Option<Self> {
+        let field = |name: &str| fields.get(name).map(|value| value.as_str());
+        let bit = parse_optional(field("bit")?)?;
+
+        Some(Self {
+            layer: field("layer")?.parse().ok()?,
+            component: Components::from_name(field("component")?)?,
+            neuron: field("neuron")?.parse().ok()?,
+            row: parse_optional(field("row")?)?,
+            col: parse_optional(field("col")?)?,
+            failure: parse_failure(field("failure")?, bit)?,
+            outcomes: field("outcomes")?.split(';').filter(|outcome| !outcome.is_empty())
+                .map(SampleOutcome::parse).collect::<Option<Vec<_>>>()?,
+            injection_times: field("injection_times")?.split(';').filter(|time| !time.is_empty())
+                .map(|time| if time == "-" || time == "null" { Some(None) } else { time.parse().ok().map(Some) })
+                .collect::<Option<Vec<_>>>()?,
+            accuracy: parse_optional(field("accuracy")?)?,
+            runtime: Duration::from_millis(field("runtime_ms")?.parse().ok()?),
+        })
+    }
+}
+
+/* a fault is identified by layer, component, neuron, fault model and bit */
+type FaultKey = (usize, &'static str, usize, &'static str, Option<usize>);
+
+/**
+Results file of a campaign, opened for appending: each result is written (and flushed) as soon as
+the simulation of its fault ends, so an interrupted campaign can be resumed skipping the faults
+already in the file. The first line of the file is the specification of the run (`Campaign::get_run_spec`),
+so that the results of another campaign or dataset are never taken for the ones of the run.
+ */
+pub struct ResultStore {
+    format: ResultFormat,
+    file: File,
+    spec: String,               /* specification of the run */
+    done: HashSet<FaultKey>,    /* faults already in the file */
+}
+
+impl ResultStore {
+    /**
+    It opens the results file of the run with the given specification, creating it if it does not exist.
+    The results already in the file are read, and an incomplete last line (left by an interrupted run) is removed.
+    - If the file has the results of another run (a different specification, or none), an error of kind InvalidInput is returned
+    - If a complete line is not a valid result, an error of kind InvalidData is returned
+     */
+    pub fn open(path: impl AsRef<Path>, format: ResultFormat, spec: &str) -> Result<Self> {
+        let mut file = OpenOptions::new().read(true).append(true).create(true).open(path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        let complete_len = contents.rfind('\n').map_or(0, |last| last + 1);
+        if complete_len < contents.len() {
+            file.set_len(complete_len as u64)?;
+        }
+        let (file_spec, lines) = split_spec(&contents[..complete_len], format);
+        if complete_len == 0 {
+            match format {
+                ResultFormat::Csv => writeln!(file, "# {}\n{}", spec, CSV_HEADER)?,
+                ResultFormat::Jsonl => writeln!(file, "{{\"spec\":\"{}\"}}", spec)?,
+            }
+        } else if file_spec != Some(spec) {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  format!("The results file belongs to another campaign or dataset: {}", file_spec.unwrap_or("no specification"))));
+        }
+        let results = parse_results(lines, format)?;
+
+        Ok(Self { format, file, spec: spec.to_string(), done: results.iter().map(|result| result.key()).collect() })
+    }
+
+    pub fn get_format(&self) -> ResultFormat { self.format }
+    pub fn get_spec(&self) -> &str { &self.spec }
+
+    /** number of faults in the file */
+    pub fn get_num_results(&self) -> usize { self.done.len() }
+
+    /** it checks whether the result of the fault of the configuration, injected in the given layer, is already in the file */
+    pub fn contains<R: Configuration>(&self, layer: usize, configuration: &R) -> bool {
+        self.done.contains(&FaultResult::new(layer, configuration).key())
+    }
+
+    /** it appends a result to the file */
+    pub fn append(&mut self, result: &FaultResult) -> Result<()> {
+        let line = match self.format {
+            ResultFormat::Csv => result.to_csv(),
+            ResultFormat::Jsonl => result.to_json(),
+        };
+        writeln!(self.file, "{}", line)?;
+        self.file.flush()?;
+        self.done.insert(result.key());
+        Ok(())
+    }
+}
+
+/**
+It reads all the results of a results file. An incomplete last line is ignored.
+ */
+pub fn read_results(path: impl AsRef<Path>, format: ResultFormat) -> Result<Vec<FaultResult>> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+
+    let complete_len = contents.rfind('\n').map_or(0, |last| last + 1);
+    parse_results(split_spec(&contents[..complete_len], format).1, format)
+}
+
+/* it splits the specification of the run (the first line, if it is one) from the other lines */
+fn split_spec(contents: &str, format: ResultFormat) -> (Option<&str>, &str) {
+    let (first, rest) = contents.split_once('\n').unwrap_or((contents, ""));
+    let spec = match format {
+        ResultFormat::Csv => first.strip_prefix("# "),
+        ResultFormat::Jsonl => first.strip_prefix("{\"spec\":\"").and_then(|spec| spec.strip_suffix("\"}")),
+    };
+    match spec {
+        Some(spec) => (Some(spec.trim_end_matches('\r')), rest),
+        None => (None, contents),
+    }
+}
+
+fn parse_results(contents: &str, format: ResultFormat) -> Result<Vec<FaultResult>> {
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+    let names: Vec<&str> = match format {
+        ResultFormat::Csv => match lines.next() {
+            Some(header) => header.split(',').collect(),
+            None => return Ok(Vec::new()),
+        },
+        ResultFormat::Jsonl => Vec::new(),
+    };
+
+    lines.map(|line| {
+        let fields = match format {
+            ResultFormat::Csv => Some(names.iter().zip(line.split(','))
+                .map(|(name, value)| (name.to_string(), value.to_string())).collect()),
+            ResultFormat::Jsonl => parse_json_fields(line),
+        };
+        fields.as_ref().and_then(FaultResult::from_fields)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("Invalid result: {}", line)))
+    }).collect()
+}
+
+/* fields of a flat JSON object, with null as empty text and the items of an array separated by ';' */
+fn parse_json_fields(line: &str) -> Option<HashMap<String, String>> {
+    let mut rest = line.trim().strip_prefix('{')?.strip_suffix('}')?.trim();
+    let mut fields = HashMap::new();
+
+    while !rest.is_empty() {
+        let (name, after_name) = json_string(rest)?;
+        rest = after_name.trim_start().strip_prefix(':')?.trim_start();
+
+        let (value, after_value) = if rest.starts_with('"') {
+            json_string(rest)?
+        } else if let Some(array) = rest.strip_prefix('[') {
+            let end = array.find(']')?;
+            let items: Vec<&str> = array[..end].split(',').map(|item| item.trim().trim_matches('"'))
+                .filter(|item| !item.is_empty()).collect();
+            (items.join(";"), &array[end + 1..])
+        } else {
+            let end = rest.find(',').unwrap_or(rest.len());
+            let value = rest[..end].trim();
+            ((if value == "null" { "" } else { value }).to_string(), &rest[end..])
+        };
+        fields.insert(name, value);
+
+        rest = after_value.trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+    Some(fields)
+}
+
+/* a JSON string (without escapes) at the beginning of the text, and the text after it */
+fn json_string(text: &str) -> Option<(String, &str)> {
+    let text = text.strip_prefix('"')?;
+    let end = text.find('"')?;
+    Some((text[..end].to_string(), &text[end + 1..]))
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or(String::new(), |value| value.to_string())
+}
+
+fn parse_optional<T: std::str::FromStr>(text: &str) -> Option<Option<T>> {
+    if text.is_empty() { Some(None) } else { text.parse().ok().map(Some) }
+}
+
+fn parse_failure(text: &str, bit: Option<usize>) -> Option<Failure> {
+    match (text, bit) {
+        ("StuckAt0", Some(bit)) => Some(Failure::StuckAt0(StuckAt0::new(bit))),
+        ("StuckAt1", Some(bit)) => Some(Failure::StuckAt1(StuckAt1::new(bit))),
+        ("TransientBitFlip", Some(bit)) => Some(Failure::TransientBitFlip(TransientBitFlip::new(bit))),
+        ("None", None) => Some(Failure::None),
+        _ => None,
+    }
+}
@@ -0,0 +1,110 @@
+/** Sparse matrix module */
+use crate::snn::float::Float;
+use crate::snn::spike_event::SpikeEvent;
+
+/** Compressed format of a sparse matrix */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SparseFormat {
+    Csr,    /* compressed rows: the weights of each neuron are contiguous */
+    Csc,    /* compressed columns: the weights of each input are contiguous */
+}
+
+/**
+Sparse matrix storing only the non-zero values, in CSR or CSC format.
+`ptr[k]..ptr[k + 1]` are the positions in `indices`/`values` of the row (CSR) or column (CSC) k.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMatrix<F: Float = f64> {
+    format: SparseFormat,
+    rows: usize,
+    cols: usize,
+    ptr: Vec<usize>,
+    indices: Vec<usize>,    /* column (CSR) or row (CSC) of each value */
+    values: Vec<F>,
+}
+
+impl<F: Float> SparseMatrix<F> {
+    pub fn from_dense(matrix: &[Vec<F>], format: SparseFormat) -> Self {
+        let rows = matrix.len();
+        let cols = matrix.first().map_or(0, |row| row.len());
+        let (outer, inner) = match format {
+            SparseFormat::Csr => (rows, cols),
+            SparseFormat::Csc => (cols, rows),
+        };
+
+        let mut ptr = Vec::with_capacity(outer + 1);
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        ptr.push(0);
+        for k in 0..outer {
+            for h in 0..inner {
+                let (i, j) = match format {
+                    SparseFormat::Csr => (k, h),
+                    SparseFormat::Csc => (h, k),
+                };
+                let val = matrix[i][j];
+                if val != F::ZERO {
+                    indices.push(h);
+                    values.push(val);
+                }
+            }
+            ptr.push(values.len());
+        }
+        Self { format, rows, cols, ptr, indices, values }
+    }
+
+    pub fn to_dense(&self) -> Vec<Vec<F>> {
+        let mut matrix = vec![vec![F::ZERO; self.cols]; self.rows];
+        for k in 0..self.values.len() {
+            let (i, j) = self.get_position(k);
+            matrix[i][j] = self.values[k];
+        }
+        matrix
+    }
+
+    pub fn get_format(&self) -> SparseFormat { self.format }
+    pub fn get_rows(&self) -> usize { self.rows }
+    pub fn get_cols(&self) -> usize { self.cols }
+    pub fn get_nnz(&self) -> usize { self.values.len() }
+    pub fn get_values(&self) -> &Vec<F> { &self.values }
+    pub fn get_values_mut(&mut self) -> &mut Vec<F> { &mut self.values }
+
+    /** (row, column) of the k-th stored value */
+    pub fn get_position(&self, k: usize) -> (usize, usize) {
+        /* outer index: the last pointer not greater than k */
+        let outer = self.ptr.partition_point(|start| *start <= k) - 1;
+        match self.format {
+            SparseFormat::Csr => (outer, self.indices[k]),
+            SparseFormat::Csc => (self.indices[k], outer),
+        }
+    }
+
+    /**
+    It computes, for each row, the sum of the values in the columns of the spike event which fired.
+    The values of each row are added in increasing column order, as in the dense computation.
+     */
+    pub fn spike_sums(&self, event: &SpikeEvent) -> Vec<F> {
+        let mut sums = vec![F::ZERO; self.rows];
+        match self.format {
+            SparseFormat::Csr => {
+                let spikes = event.get_spikes();
+                for (i, sum) in sums.iter_mut().enumerate() {
+                    for k in self.ptr[i]..self.ptr[i + 1] {
+                        if spikes.get(self.indices[k]) != 0 {
+                            *sum += self.values[k];
+                        }
+                    }
+                }
+            }
+            SparseFormat::Csc => {
+                /* scatter the columns of the inputs which fired */
+                for j in event.get_active().filter(|j| *j < self.cols) {
+                    for k in self.ptr[j]..self.ptr[j + 1] {
+                        sums[self.indices[k]] += self.values[k];
+                    }
+                }
+            }
+        }
+        sums
+    }
+}
@@ -1,20 +1,38 @@
+use crate::snn::spike_vec::SpikeVec;
+
 #[derive(Debug)]
 pub struct SpikeEvent {
     ts: u64,            /* discrete time instant */
-    spikes: Vec<u8>,    /* vector of spikes in that instant (a 1/0 for each input neuron)  */
+    spikes: SpikeVec,   /* packed spikes in that instant (a 1/0 bit for each input neuron)  */
 }
 
 impl SpikeEvent {
-    pub fn new(ts: u64, spikes: Vec<u8>) -> Self {
-        Self { ts, spikes }
+    pub fn new(ts: u64, spikes: impl Into<SpikeVec>) -> Self {
+        Self { ts, spikes: spikes.into() }
     }
 
     pub fn get_ts(&self) -> u64 {
         self.ts
     }
 
-    pub fn get_spikes(&self) -> Vec<u8> {
-        self.spikes.clone()
+    pub fn get_spikes(&self) -> &SpikeVec {
+        &self.spikes
+    }
+
+    /** indices of the neurons which fired in this instant, in increasing order (read from the packed spikes) */
+    pub fn get_active(&self) -> impl Iterator<Item = usize> + '_ {
+        self.spikes.iter_ones()
+    }
+
+    /** true if no neuron fired in this instant */
+    pub fn is_empty(&self) -> bool {
+        !self.spikes.any()
     }
 }
 
+/** Message flowing between the layers of a network processing a stream of samples */
+#[derive(Debug)]
+pub enum SpikeMessage {
+    Event(SpikeEvent),  /* spikes of an instant of the current sample */
+    Reset,              /* end of the sample: the state of the layers is reset */
+}
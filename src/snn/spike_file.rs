@@ -0,0 +1,238 @@
+/** Binary spike file module */
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
+use std::path::Path;
+use crate::snn::spike_reader::SpikeReader;
+use crate::snn::spike_vec::SpikeVec;
+
+const MAGIC: &[u8; 8] = b"SNNSPIKE";
+const VERSION: u16 = 1;
+const NUM_SAMPLES_OFFSET: u64 = 20;   /* position of the number of samples in the header */
+
+/** Encoding of the samples of a binary spike file */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpikeEncoding {
+    Dense,      /* for each input neuron, its spikes packed along the instants in 64 bit words */
+    Events,     /* the number of spikes, then an (instant, neuron) pair for each spike in order of time */
+}
+
+/**
+Header of a binary spike file (24 bytes, little endian): the magic string "SNNSPIKE", the version (u16),
+the encoding (u16, 0 dense and 1 events) and the number of inputs, of instants of each sample and of samples (u32).
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpikeFileHeader {
+    pub encoding: SpikeEncoding,
+    pub num_inputs: usize,
+    pub num_instants: usize,
+    pub num_samples: usize,
+}
+
+impl SpikeFileHeader {
+    fn write<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        let encoding: u16 = match self.encoding {
+            SpikeEncoding::Dense => 0,
+            SpikeEncoding::Events => 1,
+        };
+        writer.write_all(&encoding.to_le_bytes())?;
+        for value in [self.num_inputs, self.num_instants, self.num_samples] {
+            writer.write_all(&to_u32(value)?.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn read<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut bytes = [0u8; 24];
+        reader.read_exact(&mut bytes)?;
+        if &bytes[0..8] != MAGIC {
+            return Err(Error::new(ErrorKind::InvalidData, "Not a binary spike file"));
+        }
+        let version = u16::from_le_bytes([bytes[8], bytes[9]]);
+        if version != VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, format!("Unsupported version {} of the spike file", version)));
+        }
+        let encoding = match u16::from_le_bytes([bytes[10], bytes[11]]) {
+            0 => SpikeEncoding::Dense,
+            1 => SpikeEncoding::Events,
+            encoding => return Err(Error::new(ErrorKind::InvalidData, format!("Unknown encoding {} of the spike file", encoding))),
+        };
+        let field = |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+
+        Ok(Self { encoding, num_inputs: field(12), num_instants: field(16), num_samples: field(20) })
+    }
+}
+
+/**
+Writer of a binary spike file. The number of samples in the header is written by `finish`.
+ */
+pub struct SpikeFileWriter<W: Write + Seek> {
+    writer: W,
+    header: SpikeFileHeader,
+}
+
+impl SpikeFileWriter<BufWriter<File>> {
+    pub fn create(path: impl AsRef<Path>, encoding: SpikeEncoding, num_inputs: usize, num_instants: usize) -> Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), encoding, num_inputs, num_instants)
+    }
+}
+
+impl<W: Write + Seek> SpikeFileWriter<W> {
+    /** it writes the header of a file with no samples */
+    pub fn new(mut writer: W, encoding: SpikeEncoding, num_inputs: usize, num_instants: usize) -> Result<Self> {
+        let header = SpikeFileHeader { encoding, num_inputs, num_instants, num_samples: 0 };
+        header.write(&mut writer)?;
+        Ok(Self { writer, header })
+    }
+
+    pub fn get_header(&self) -> SpikeFileHeader { self.header }
+
+    /**
+    It appends a sample given as [neuron][instant].
+    - If the sample has not the number of inputs and instants of the file, an error of kind InvalidInput is returned
+     */
+    pub fn write_sample(&mut self, spikes: &[Vec<u8>]) -> Result<()> {
+        if spikes.len() != self.header.num_inputs || spikes.iter().any(|neuron_spikes| neuron_spikes.len() != self.header.num_instants) {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  format!("The samples of the file must have {} inputs of {} instants", self.header.num_inputs, self.header.num_instants)));
+        }
+
+        match self.header.encoding {
+            SpikeEncoding::Dense => {
+                for neuron_spikes in spikes {
+                    for word in SpikeVec::from(neuron_spikes.as_slice()).get_words() {
+                        self.writer.write_all(&word.to_le_bytes())?;
+                    }
+                }
+            }
+            SpikeEncoding::Events => {
+                let events: Vec<(usize, usize)> = (0..self.header.num_instants)
+                    .flat_map(|instant| spikes.iter().enumerate()
+                        .filter(move |(_, neuron_spikes)| neuron_spikes[instant] != 0)
+                        .map(move |(neuron, _)| (instant, neuron)))
+                    .collect();
+                self.writer.write_all(&to_u32(events.len())?.to_le_bytes())?;
+                for (instant, neuron) in events {
+                    self.writer.write_all(&to_u32(instant)?.to_le_bytes())?;
+                    self.writer.write_all(&to_u32(neuron)?.to_le_bytes())?;
+                }
+            }
+        }
+        self.header.num_samples += 1;
+        Ok(())
+    }
+
+    /** it writes the number of samples into the header and returns the underlying writer */
+    pub fn finish(mut self) -> Result<W> {
+        self.writer.seek(SeekFrom::Start(NUM_SAMPLES_OFFSET))?;
+        self.writer.write_all(&to_u32(self.header.num_samples)?.to_le_bytes())?;
+        self.writer.seek(SeekFrom::End(0))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/**
+Reader of a binary spike file: the samples are decoded one at a time, as [neuron][instant]
+(or packed, for `SNN::process_packed`).
+ */
+pub struct SpikeFileReader<R: Read> {
+    reader: R,
+    header: SpikeFileHeader,
+    num_read: usize,    /* samples read */
+}
+
+impl SpikeFileReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> SpikeFileReader<R> {
+    /**
+    It reads the header of the file.
+    - If the header is not valid, an error of kind InvalidData is returned
+     */
+    pub fn new(mut reader: R) -> Result<Self> {
+        let header = SpikeFileHeader::read(&mut reader)?;
+        Ok(Self { reader, header, num_read: 0 })
+    }
+
+    pub fn get_header(&self) -> SpikeFileHeader { self.header }
+
+    /**
+    It decodes the next sample with the spikes of each input neuron packed along the instants, None after the last one.
+    - If an event is out of the inputs or instants of the file, an error of kind InvalidData is returned
+     */
+    pub fn next_packed_sample(&mut self) -> Result<Option<Vec<SpikeVec>>> {
+        if self.num_read == self.header.num_samples {
+            return Ok(None);
+        }
+        let (num_inputs, num_instants) = (self.header.num_inputs, self.header.num_instants);
+
+        let spikes = match self.header.encoding {
+            SpikeEncoding::Dense => {
+                let num_words = num_instants.div_ceil(64);
+                let mut bytes = vec![0u8; num_words * 8];
+                (0..num_inputs).map(|_| {
+                    self.reader.read_exact(&mut bytes)?;
+                    let words = bytes.chunks_exact(8).map(|word| u64::from_le_bytes(word.try_into().unwrap())).collect();
+                    Ok(SpikeVec::from_words(num_instants, words))
+                }).collect::<Result<Vec<SpikeVec>>>()?
+            }
+            SpikeEncoding::Events => {
+                let mut spikes = vec![SpikeVec::new(num_instants); num_inputs];
+                for _ in 0..self.read_u32()? {
+                    let (instant, neuron) = (self.read_u32()? as usize, self.read_u32()? as usize);
+                    if instant >= num_instants || neuron >= num_inputs {
+                        return Err(Error::new(ErrorKind::InvalidData, format!("Spike event ({}, {}) out of the sample", instant, neuron)));
+                    }
+                    spikes[neuron].set(instant, 1);
+                }
+                spikes
+            }
+        };
+
+        self.num_read += 1;
+        Ok(Some(spikes))
+    }
+
+    /** it decodes the next sample as [neuron][instant], None after the last one */
+    pub fn next_sample(&mut self) -> Result<Option<Vec<Vec<u8>>>> {
+        Ok(self.next_packed_sample()?.map(|spikes| spikes.iter().map(|neuron_spikes| neuron_spikes.to_vec()).collect()))
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let mut bytes = [0u8; 4];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+}
+
+impl<R: Read> Iterator for SpikeFileReader<R> {
+    type Item = Result<Vec<Vec<u8>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_sample().transpose()
+    }
+}
+
+/**
+It converts a spike file read by `SpikeReader` (text, zip archive or binary) into a binary spike file
+with the given encoding, and returns the number of samples converted.
+ */
+pub fn convert_spike_file(input: impl AsRef<Path>, output: impl AsRef<Path>, num_inputs: usize, num_instants: usize,
+                          encoding: SpikeEncoding) -> Result<usize> {
+    let mut writer = SpikeFileWriter::create(output, encoding, num_inputs, num_instants)?;
+    for sample in SpikeReader::open(input, num_inputs, num_instants)? {
+        writer.write_sample(&sample?)?;
+    }
+    let num_samples = writer.get_header().num_samples;
+    writer.finish()?;
+    Ok(num_samples)
+}
+
+fn to_u32(value: usize) -> Result<u32> {
+    u32::try_from(value).map_err(|_| Error::new(ErrorKind::InvalidInput, format!("{} does not fit the spike file", value)))
+}
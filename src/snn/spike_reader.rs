@@ -0,0 +1,141 @@
+/** Spike file reader module */
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Result};
+use std::path::Path;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::thread;
+use zip::read::ZipArchive;
+use crate::snn::spike_file::SpikeFileReader;
+
+/**
+Decoder of the samples of a text spike file: a line for each instant, with a '0' or '1' for each input neuron,
+and `num_instants` lines for each sample. The samples are decoded one at a time, as [neuron][instant].
+ */
+pub struct SpikeDecoder<R: BufRead> {
+    reader: R,
+    num_inputs: usize,
+    num_instants: usize,
+    line: String,   /* buffer of the line being decoded */
+}
+
+impl<R: BufRead> SpikeDecoder<R> {
+    pub fn new(reader: R, num_inputs: usize, num_instants: usize) -> Self {
+        Self { reader, num_inputs, num_instants, line: String::new() }
+    }
+
+    pub fn get_num_inputs(&self) -> usize { self.num_inputs }
+    pub fn get_num_instants(&self) -> usize { self.num_instants }
+
+    /**
+    It decodes the next sample, None at the end of the file.
+    - If a line has not `num_inputs` spikes or contains other characters, an error of kind InvalidData is returned
+    - If the file ends in the middle of a sample, an error of kind UnexpectedEof is returned
+     */
+    pub fn next_sample(&mut self) -> Result<Option<Vec<Vec<u8>>>> {
+        let mut spikes = vec![vec![0u8; self.num_instants]; self.num_inputs];
+
+        for instant in 0..self.num_instants {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return match instant {
+                    0 => Ok(None),
+                    _ => Err(Error::new(ErrorKind::UnexpectedEof,
+                                        format!("The spike file ends after {} instants of a sample of {}", instant, self.num_instants))),
+                };
+            }
+
+            let line = self.line.trim_end_matches(['\n', '\r']);
+            if line.len() != self.num_inputs {
+                return Err(Error::new(ErrorKind::InvalidData,
+                                      format!("Expected {} spikes in an instant, found {}", self.num_inputs, line.len())));
+            }
+            for (neuron_spikes, spike) in spikes.iter_mut().zip(line.bytes()) {
+                neuron_spikes[instant] = match spike {
+                    b'0' => 0,
+                    b'1' => 1,
+                    _ => return Err(Error::new(ErrorKind::InvalidData, format!("Invalid spike '{}'", spike as char))),
+                };
+            }
+        }
+        Ok(Some(spikes))
+    }
+}
+
+impl<R: BufRead> Iterator for SpikeDecoder<R> {
+    type Item = Result<Vec<Vec<u8>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_sample().transpose()
+    }
+}
+
+/**
+Reader of the samples of a spike file, in text format (see `SpikeDecoder`) or compressed as the first entry
+of a zip archive (`.zip` extension), or in binary format (`.spk` extension, see `SpikeFileReader`). The zip entry is decompressed and decoded by a thread while it is read,
+one sample ahead of the iteration, so the dataset is never extracted to disk nor loaded all in memory.
+ */
+pub struct SpikeReader {
+    samples: Receiver<Result<Vec<Vec<u8>>>>,
+}
+
+impl SpikeReader {
+    /**
+    It opens the spike file. The errors in the samples are returned by the iteration, after which it ends.
+    - If a binary file has not `num_inputs` inputs and `num_instants` instants, an error of kind InvalidData is returned
+     */
+    pub fn open(path: impl AsRef<Path>, num_inputs: usize, num_instants: usize) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        /* (the decoder thread stays at most one sample ahead) */
+        let (samples_tx, samples) = sync_channel::<Result<Vec<Vec<u8>>>>(0);
+
+        if path.extension().is_some_and(|extension| extension == "zip") {
+            let mut archive = ZipArchive::new(file)?;
+            archive.by_index(0)?;
+
+            thread::spawn(move || {
+                let entry = match archive.by_index(0) {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        let _ = samples_tx.send(Err(e.into()));
+                        return;
+                    }
+                };
+                send_samples(SpikeDecoder::new(BufReader::new(entry), num_inputs, num_instants), samples_tx);
+            });
+        } else if path.extension().is_some_and(|extension| extension == "spk") {
+            let reader = SpikeFileReader::new(BufReader::new(file))?;
+            let header = reader.get_header();
+            if header.num_inputs != num_inputs || header.num_instants != num_instants {
+                return Err(Error::new(ErrorKind::InvalidData,
+                                      format!("The spike file has samples of {} inputs and {} instants, expected {} and {}",
+                                              header.num_inputs, header.num_instants, num_inputs, num_instants)));
+            }
+            thread::spawn(move || send_samples(reader, samples_tx));
+        } else {
+            thread::spawn(move || {
+                send_samples(SpikeDecoder::new(BufReader::new(file), num_inputs, num_instants), samples_tx);
+            });
+        }
+
+        Ok(Self { samples })
+    }
+}
+
+impl Iterator for SpikeReader {
+    type Item = Result<Vec<Vec<u8>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.samples.recv().ok()
+    }
+}
+
+/* it sends the decoded samples until the first error, or until the reader is dropped (the send fails) */
+fn send_samples(decoder: impl Iterator<Item = Result<Vec<Vec<u8>>>>, samples_tx: SyncSender<Result<Vec<Vec<u8>>>>) {
+    for sample in decoder {
+        let error = sample.is_err();
+        if samples_tx.send(sample).is_err() || error {
+            return;
+        }
+    }
+}
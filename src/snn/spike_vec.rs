@@ -0,0 +1,123 @@
+/** Spike vector module */
+const WORD_BITS: usize = 64;
+
+/**
+Vector of spikes packed in 64 bit words, one bit for each spike (bit i%64 of the word i/64).
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpikeVec {
+    len: usize,         /* number of spikes */
+    words: Vec<u64>,    /* packed spikes, the unused bits of the last word are 0 */
+}
+
+impl SpikeVec {
+    /** vector of `len` spikes all equal to 0 */
+    pub fn new(len: usize) -> Self {
+        Self { len, words: vec![0; len.div_ceil(WORD_BITS)] }
+    }
+
+    /**
+    vector of `len` spikes packed in the given words (bit i%64 of the word i/64), the bits beyond `len` are ignored
+    - If the number of words is not the one needed for `len` spikes, the process panics
+     */
+    pub fn from_words(len: usize, mut words: Vec<u64>) -> Self {
+        if words.len() != len.div_ceil(WORD_BITS) {
+            panic!("{} spikes need {} words, {} given", len, len.div_ceil(WORD_BITS), words.len());
+        }
+        let unused = words.len() * WORD_BITS - len;
+        if let Some(last) = words.last_mut() {
+            *last &= u64::MAX >> unused;
+        }
+        Self { len, words }
+    }
+
+    pub fn len(&self) -> usize { self.len }
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+    pub fn get_words(&self) -> &[u64] { &self.words }
+
+    /** spike in position i, 0 if i is out of range */
+    pub fn get(&self, i: usize) -> u8 {
+        if i >= self.len {
+            return 0;
+        }
+        ((self.words[i / WORD_BITS] >> (i % WORD_BITS)) & 1) as u8
+    }
+
+    /** it sets the spike in position i (any non-zero value is a spike) */
+    pub fn set(&mut self, i: usize, spike: u8) {
+        if i >= self.len {
+            panic!("Spike index {} out of range for a vector of {} spikes", i, self.len);
+        }
+        if spike != 0 {
+            self.words[i / WORD_BITS] |= 1 << (i % WORD_BITS);
+        } else {
+            self.words[i / WORD_BITS] &= !(1 << (i % WORD_BITS));
+        }
+    }
+
+    pub fn push(&mut self, spike: u8) {
+        if self.len.is_multiple_of(WORD_BITS) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, spike);
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.words.clear();
+    }
+
+    /** number of spikes equal to 1 */
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /** true if at least one spike is 1 */
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|word| *word != 0)
+    }
+
+    /** spikes as 0/1 values */
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+
+    /** positions of the spikes equal to 1, in increasing order */
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(w, word)| {
+            let mut bits = *word;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let bit = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(w * WORD_BITS + bit)
+            })
+        })
+    }
+
+    /** unpacked copy of the spikes, one u8 for each spike */
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.iter().collect()
+    }
+}
+
+impl From<&[u8]> for SpikeVec {
+    fn from(spikes: &[u8]) -> Self {
+        let mut vec = SpikeVec::new(spikes.len());
+        for (i, spike) in spikes.iter().enumerate() {
+            if *spike != 0 {
+                vec.set(i, 1);
+            }
+        }
+        vec
+    }
+}
+
+impl From<Vec<u8>> for SpikeVec {
+    fn from(spikes: Vec<u8>) -> Self {
+        SpikeVec::from(spikes.as_slice())
+    }
+}
@@ -0,0 +1,128 @@
+/** SVG report module */
+use std::collections::HashSet;
+use std::fmt::Write;
+use crate::snn::recording::SpikeRecord;
+
+const PLOT_WIDTH: f64 = 800.0;
+const PLOT_HEIGHT: f64 = 400.0;
+const MARGIN: f64 = 50.0;
+
+const COLOR: &str = "#000000";          /* spikes (or rates) of the run, same as the golden ones */
+const GOLDEN_COLOR: &str = "#1f77b4";   /* golden spikes missing in the run, golden rates */
+const FAULTY_COLOR: &str = "#d62728";   /* spikes of the run missing in the golden one, rates of the run */
+
+/**
+It renders the spikes of a layer as a raster plot (one row for each neuron, one column for each instant)
+in a standalone SVG document. If the golden spikes are given they are overlaid: the spikes of both runs are
+black, the golden spikes missing in the run are blue and the spikes of the run missing in the golden one are red.
+ */
+pub fn raster_svg(title: &str, record: &SpikeRecord, duration: usize, golden: Option<&SpikeRecord>) -> String {
+    let num_neurons = record.get_num_neurons().max(golden.map_or(0, |golden| golden.get_num_neurons()));
+    let dx = PLOT_WIDTH / duration.max(1) as f64;
+    let dy = PLOT_HEIGHT / num_neurons.max(1) as f64;
+
+    let mut svg = header(title);
+    axes(&mut svg, "instant", &duration.to_string(), "neuron", &num_neurons.to_string());
+
+    let spikes: HashSet<(u64, usize)> = record.get_spikes().iter().copied().collect();
+    let golden_spikes: HashSet<(u64, usize)> = golden.map_or(HashSet::new(), |golden| golden.get_spikes().iter().copied().collect());
+
+    let mut draw = |(instant, neuron): (u64, usize), color: &str| {
+        let x = MARGIN + instant as f64 * dx;
+        let y = MARGIN + (num_neurons - 1 - neuron) as f64 * dy;
+        let _ = writeln!(svg, r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{}"/>"#,
+                         x, y, dx.max(1.0), (dy * 0.8).max(1.0), color);
+    };
+    for spike in record.get_spikes() {
+        let color = if golden.is_none() || golden_spikes.contains(spike) { COLOR } else { FAULTY_COLOR };
+        draw(*spike, color);
+    }
+    if let Some(golden) = golden {
+        for spike in golden.get_spikes().iter().filter(|spike| !spikes.contains(spike)) {
+            draw(*spike, GOLDEN_COLOR);
+        }
+    }
+
+    if golden.is_some() {
+        legend(&mut svg, &[(COLOR, "both"), (GOLDEN_COLOR, "golden only"), (FAULTY_COLOR, "faulty only")]);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/**
+It renders the firing rate (spikes per instant) of each neuron of a layer as a histogram in a standalone
+SVG document. If the golden spikes are given, the golden rate (blue) is drawn beside the one of the run (red).
+ */
+pub fn firing_rate_svg(title: &str, record: &SpikeRecord, duration: usize, golden: Option<&SpikeRecord>) -> String {
+    let rates = record.get_firing_rates(duration);
+    let golden_rates = golden.map(|golden| golden.get_firing_rates(duration));
+    let num_neurons = rates.len().max(golden_rates.as_ref().map_or(0, |rates| rates.len()));
+
+    let max_rate = rates.iter().chain(golden_rates.iter().flatten()).fold(0.0f64, |max, rate| max.max(*rate));
+    let scale = if max_rate > 0.0 { PLOT_HEIGHT / max_rate } else { 0.0 };
+    let dx = PLOT_WIDTH / num_neurons.max(1) as f64;
+
+    let mut svg = header(title);
+    axes(&mut svg, "neuron", &num_neurons.to_string(), "rate", &format!("{:.3}", max_rate));
+
+    let mut bar = |neuron: usize, rate: f64, offset: f64, width: f64, color: &str| {
+        let height = rate * scale;
+        let _ = writeln!(svg, r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{}"/>"#,
+                         MARGIN + neuron as f64 * dx + offset, MARGIN + PLOT_HEIGHT - height, width, height, color);
+    };
+    match golden_rates.as_ref() {
+        Some(golden_rates) => {
+            for neuron in 0..num_neurons {
+                bar(neuron, golden_rates.get(neuron).copied().unwrap_or(0.0), 0.0, dx * 0.45, GOLDEN_COLOR);
+                bar(neuron, rates.get(neuron).copied().unwrap_or(0.0), dx * 0.45, dx * 0.45, FAULTY_COLOR);
+            }
+        }
+        None => rates.iter().enumerate().for_each(|(neuron, rate)| bar(neuron, *rate, 0.0, dx * 0.9, COLOR)),
+    }
+
+    if golden_rates.is_some() {
+        legend(&mut svg, &[(GOLDEN_COLOR, "golden"), (FAULTY_COLOR, "faulty")]);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn header(title: &str) -> String {
+    let width = PLOT_WIDTH + 2.0 * MARGIN;
+    let height = PLOT_HEIGHT + 2.0 * MARGIN;
+    let mut svg = String::new();
+    let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#, width, height);
+    let _ = writeln!(svg, r#"<rect width="{}" height="{}" fill="white"/>"#, width, height);
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" font-family="sans-serif" font-size="16" text-anchor="middle">{}</text>"#,
+                     width / 2.0, MARGIN / 2.0, escape(title));
+    svg
+}
+
+/* x and y axes with the labels and the maximum value of each one */
+fn axes(svg: &mut String, x_label: &str, x_max: &str, y_label: &str, y_max: &str) {
+    let (left, right, top, bottom) = (MARGIN, MARGIN + PLOT_WIDTH, MARGIN, MARGIN + PLOT_HEIGHT);
+    let _ = writeln!(svg, r#"<g stroke="black" stroke-width="1"><line x1="{0}" y1="{1}" x2="{2}" y2="{1}"/><line x1="{0}" y1="{1}" x2="{0}" y2="{3}"/></g>"#,
+                     left, bottom, right, top);
+    let _ = writeln!(svg, r#"<g font-family="sans-serif" font-size="12">"#);
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#, (left + right) / 2.0, bottom + 35.0, escape(x_label));
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" text-anchor="middle">0</text>"#, left, bottom + 15.0);
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#, right, bottom + 15.0, escape(x_max));
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" text-anchor="middle" transform="rotate(-90 {0} {1})">{}</text>"#,
+                     left - 25.0, (top + bottom) / 2.0, escape(y_label));
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" text-anchor="end">{}</text>"#, left - 5.0, top + 4.0, escape(y_max));
+    let _ = writeln!(svg, "</g>");
+}
+
+fn legend(svg: &mut String, entries: &[(&str, &str)]) {
+    for (k, (color, label)) in entries.iter().enumerate() {
+        let x = MARGIN + PLOT_WIDTH - 110.0;
+        let y = MARGIN / 2.0 + 14.0 * k as f64 - 10.0;
+        let _ = writeln!(svg, r#"<rect x="{}" y="{}" width="10" height="10" fill="{}"/><text x="{}" y="{}" font-family="sans-serif" font-size="11">{}</text>"#,
+                         x, y, color, x + 14.0, y + 9.0, escape(label));
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,119 @@
+/** TMR module */
+use crate::neuron::Neuron;
+use crate::snn::float::Float;
+
+/**
+Triple Modular Redundancy of a Neuron.
+It holds three replicas of the same neuron, advances all of them and returns the majority of their output spikes.
+Setters only write the first replica, so that a fault injected by the Layer lands in one replica only.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct TmrNeuron<N: Neuron + Clone> {
+    replicas: [N; 3],   /* copies of the neuron, the first one is the faulty one */
+    vote_state: bool,   /* if true v_mem and ts are voted too and written back to all the replicas */
+    disagreements: u64, /* number of instants in which the replicas did not produce the same spike */
+}
+
+impl<N: Neuron + Clone> TmrNeuron<N> {
+    pub fn new(neuron: N, vote_state: bool) -> Self {
+        Self {
+            replicas: [neuron.clone(), neuron.clone(), neuron],
+            vote_state,
+            disagreements: 0,
+        }
+    }
+    pub fn get_replicas(&self) -> Vec<N> {
+        self.replicas.to_vec()
+    }
+    pub fn get_vote_state(&self) -> bool {
+        self.vote_state
+    }
+    pub fn get_disagreements(&self) -> u64 {
+        self.disagreements
+    }
+}
+
+/* bitwise majority of three words */
+fn vote(a: u64, b: u64, c: u64) -> u64 {
+    (a & b) | (a & c) | (b & c)
+}
+
+impl<N: Neuron + Clone> Neuron for TmrNeuron<N> {
+    type Float = N::Float;
+
+    fn get_v_th(&self) -> N::Float {
+        self.replicas[0].get_v_th()
+    }
+    fn set_v_th(&mut self, new_val: N::Float) { self.replicas[0].set_v_th(new_val) }
+    fn calculate_v_mem(&mut self, t: u64, extra_intra_sum: N::Float) -> u8 {
+        let spikes: Vec<u8> = self.replicas.iter_mut()
+            .map(|replica| replica.calculate_v_mem(t, extra_intra_sum))
+            .collect();
+
+        if spikes[0] != spikes[1] || spikes[0] != spikes[2] {
+            self.disagreements += 1;
+        }
+
+        if self.vote_state {
+            let v_mem = vote(self.replicas[0].get_v_mem().to_word(),
+                             self.replicas[1].get_v_mem().to_word(),
+                             self.replicas[2].get_v_mem().to_word());
+            let ts = vote(self.replicas[0].get_ts(), self.replicas[1].get_ts(), self.replicas[2].get_ts());
+            for replica in self.replicas.iter_mut() {
+                replica.set_v_mem(N::Float::from_word(v_mem));
+                replica.set_ts(ts);
+            }
+        }
+
+        vote(spikes[0] as u64, spikes[1] as u64, spikes[2] as u64) as u8
+    }
+    fn init(&mut self) {
+        self.replicas.iter_mut().for_each(|replica| replica.init());
+        self.disagreements = 0;
+    }
+    fn get_tau(&self) -> N::Float {
+        self.replicas[0].get_tau()
+    }
+    fn get_v_reset(&self) -> N::Float {
+        self.replicas[0].get_v_reset()
+    }
+    fn get_v_rest(&self) -> N::Float {
+        self.replicas[0].get_v_rest()
+    }
+    fn get_ts(&self) -> u64 {
+        self.replicas[0].get_ts()
+    }
+    fn get_v_mem(&self) -> N::Float {
+        self.replicas[0].get_v_mem()
+    }
+    fn set_v_mem(&mut self, val: N::Float) {
+        self.replicas[0].set_v_mem(val);
+    }
+    fn set_tau(&mut self, val: N::Float) {
+        self.replicas[0].set_tau(val);
+    }
+    fn set_v_reset(&mut self, val: N::Float) {
+        self.replicas[0].set_v_reset(val);
+    }
+    fn set_v_rest(&mut self, val: N::Float) {
+        self.replicas[0].set_v_rest(val);
+    }
+    fn set_ts(&mut self, val: u64) {
+        self.replicas[0].set_ts(val);
+    }
+    fn get_dt(&self) -> N::Float {
+        self.replicas[0].get_dt()
+    }
+    fn set_dt(&mut self, val: N::Float) {
+        self.replicas[0].set_dt(val);
+    }
+    fn get_word_width(&self) -> usize {
+        self.replicas[0].get_word_width()
+    }
+    fn encode_word(&self, val: N::Float) -> u64 {
+        self.replicas[0].encode_word(val)
+    }
+    fn decode_word(&self, word: u64) -> N::Float {
+        self.replicas[0].decode_word(word)
+    }
+}
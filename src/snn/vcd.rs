@@ -0,0 +1,112 @@
+/** VCD (Value Change Dump) export module */
+use std::collections::BTreeMap;
+use std::io::{Result, Write};
+use crate::snn::float::Float;
+use crate::snn::probe::Probe;
+use crate::snn::recording::SpikeRecord;
+
+/**
+Signals recorded in a run of a network: the spikes of each layer and the membrane potentials of the probed neurons.
+Each run is written in its own scope, so that golden and faulty runs can be compared side by side.
+ */
+#[derive(Debug, Clone)]
+pub struct VcdRun<F: Float = f64> {
+    name: String,
+    records: Vec<SpikeRecord>,  /* spikes of each layer */
+    probes: Vec<Probe<F>>,
+}
+
+impl<F: Float> VcdRun<F> {
+    pub fn new(name: &str, records: Vec<SpikeRecord>, probes: Vec<Probe<F>>) -> Self {
+        /* spaces are not allowed in the identifiers of a VCD file */
+        Self { name: name.replace(char::is_whitespace, "_"), records, probes }
+    }
+}
+
+/**
+It writes the runs as a VCD file with a time unit for each instant: a scope for each run containing
+a scope for each layer, with a 1 bit signal for each neuron (high for the instant of each spike)
+and a real signal with the membrane potential of each probed neuron.
+ */
+pub fn write_vcd<W: Write, F: Float>(writer: &mut W, runs: &[VcdRun<F>]) -> Result<()> {
+    let mut num_vars = 0;
+    let mut changes = BTreeMap::<u64, Vec<String>>::new();
+    let mut initial_values = Vec::<String>::new();
+
+    writeln!(writer, "$version spiking_neural_network $end")?;
+    writeln!(writer, "$timescale 1 ns $end")?;
+
+    for run in runs {
+        writeln!(writer, "$scope module {} $end", run.name)?;
+
+        let num_layers = run.records.len().max(run.probes.iter().map(|probe| probe.get_layer() + 1).max().unwrap_or(0));
+        for layer in 0..num_layers {
+            writeln!(writer, "$scope module layer_{} $end", layer)?;
+
+            /* spikes: 1 at the instant of the spike, 0 at the next one if the neuron does not fire again */
+            if let Some(record) = run.records.get(layer) {
+                let mut spike_instants = vec![Vec::<u64>::new(); record.get_num_neurons()];
+                record.get_spikes().iter().for_each(|(instant, neuron)| spike_instants[*neuron].push(*instant));
+
+                for (neuron, instants) in spike_instants.iter().enumerate() {
+                    let id = var_id(num_vars);
+                    num_vars += 1;
+                    writeln!(writer, "$var wire 1 {} n{} $end", id, neuron)?;
+                    initial_values.push(format!("0{}", id));
+
+                    for (k, instant) in instants.iter().enumerate() {
+                        changes.entry(*instant).or_default().push(format!("1{}", id));
+                        if instants.get(k + 1) != Some(&(instant + 1)) {
+                            changes.entry(instant + 1).or_default().push(format!("0{}", id));
+                        }
+                    }
+                }
+            }
+
+            for probe in run.probes.iter().filter(|probe| probe.get_layer() == layer) {
+                let id = var_id(num_vars);
+                num_vars += 1;
+                writeln!(writer, "$var real 64 {} v_mem_n{} $end", id, probe.get_neuron())?;
+
+                for (instant, v_mem) in probe.get_v_mem() {
+                    changes.entry(instant).or_default().push(format!("r{:e} {}", v_mem.to_f64(), id));
+                }
+            }
+
+            writeln!(writer, "$upscope $end")?;
+        }
+
+        writeln!(writer, "$upscope $end")?;
+    }
+    writeln!(writer, "$enddefinitions $end")?;
+
+    writeln!(writer, "#0")?;
+    writeln!(writer, "$dumpvars")?;
+    for value in initial_values {
+        writeln!(writer, "{}", value)?;
+    }
+    writeln!(writer, "$end")?;
+
+    for (instant, values) in changes {
+        if instant != 0 {
+            writeln!(writer, "#{}", instant)?;
+        }
+        for value in values {
+            writeln!(writer, "{}", value)?;
+        }
+    }
+    Ok(())
+}
+
+/* identifier of the n-th variable, made of the printable characters from '!' to '~' */
+fn var_id(mut n: usize) -> String {
+    let mut id = String::new();
+    loop {
+        id.push((b'!' + (n % 94) as u8) as char);
+        n /= 94;
+        if n == 0 {
+            return id;
+        }
+        n -= 1;
+    }
+}
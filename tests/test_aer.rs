@@ -0,0 +1,81 @@
+use std::io::{Cursor, ErrorKind};
+use spiking_neural_network::aer::{aer_to_spikes, read_aer, spikes_to_aer, write_aer, AerEvent};
+use spiking_neural_network::failure::{Conf, Failure};
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::network::{Executor, SNN};
+use spiking_neural_network::snn::builder::SnnBuilder;
+
+fn create_network() -> SNN<LifNeuron, Conf> {
+    SnnBuilder::<LifNeuron, Conf>::new(3)
+        .add_layer(vec![LifNeuron::new(0.6, 0.0, 0.1, 1.0, 0.5); 3],
+                   vec![vec![0.5, 0.3, 0.0], vec![0.1, 0.4, 0.4], vec![0.0, 0.2, 0.7]],
+                   vec![vec![0.0, -0.1, -0.2], vec![-0.1, 0.0, -0.1], vec![-0.2, -0.1, 0.0]],
+                   Conf::new(vec![], Failure::None, 0))
+        .add_layer(vec![LifNeuron::new(0.3, 0.0, 0.0, 1.0, 0.5); 2],
+                   vec![vec![0.4, 0.1, 0.2], vec![0.0, 0.3, 0.5]],
+                   vec![vec![0.0, -0.05], vec![-0.05, 0.0]],
+                   Conf::new(vec![], Failure::None, 0))
+        .build()
+}
+
+fn create_spikes() -> Vec<Vec<u8>> {
+    vec![vec![1, 0, 1, 1, 0, 0, 1], vec![0, 1, 1, 0, 0, 0, 1], vec![1, 1, 0, 0, 0, 0, 1]]
+}
+
+#[test]
+fn verify_spikes_conversion() {
+    let events = spikes_to_aer(&create_spikes());
+
+    assert_eq!(events.len(), 10);
+    assert_eq!(&events[0..3], &[AerEvent::new(0, 0), AerEvent::new(0, 2), AerEvent::new(1, 1)]);
+    assert_eq!(aer_to_spikes(&events, 3, 7), create_spikes());
+}
+
+#[test]
+fn verify_process_aer() {
+    let spikes = create_spikes();
+    let mut events = spikes_to_aer(&spikes);
+    events.reverse();   /* the input events can be in any order */
+
+    for executor in [Executor::Threaded, Executor::Lockstep] {
+        let mut snn = create_network();
+        snn.set_executor(executor);
+        let expected = snn.process(&spikes);
+
+        let output = snn.process_aer(&events);
+        assert!(output.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(aer_to_spikes(&output, 2, 7), expected);
+    }
+}
+
+#[test]
+fn verify_process_aer_sparse() {
+    /* instants far apart are processed without expanding the input */
+    let events = vec![AerEvent::new(1_000_000, 2), AerEvent::new(5, 0), AerEvent::new(5, 0)];
+    let mut snn = create_network();
+
+    let output = snn.process_aer(&events);
+    assert!(output.iter().all(|event| event.timestamp == 5 || event.timestamp == 1_000_000));
+    assert!(snn.process_aer(&[]).is_empty());
+}
+
+#[test]
+#[should_panic]
+fn verify_process_aer_invalid_address() {
+    create_network().process_aer(&[AerEvent::new(0, 3)]);
+}
+
+#[test]
+fn verify_text_format() {
+    let events = spikes_to_aer(&create_spikes());
+    let mut text = Vec::new();
+    write_aer(&mut text, &events).unwrap();
+    assert!(text.starts_with(b"0 0\n0 2\n1 1\n"));
+    assert_eq!(read_aer(Cursor::new(text)).unwrap(), events);
+
+    let text = "# timestamp, address\n10,3\n\n  12 1  # comment\n";
+    assert_eq!(read_aer(Cursor::new(text)).unwrap(), vec![AerEvent::new(10, 3), AerEvent::new(12, 1)]);
+
+    assert_eq!(read_aer(Cursor::new("10 3 4\n")).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(read_aer(Cursor::new("10 x\n")).unwrap_err().kind(), ErrorKind::InvalidData);
+}
@@ -0,0 +1,91 @@
+use rand::Rng;
+use spiking_neural_network::campaign::{Campaign, derive_seed, rng_from_seed};
+use spiking_neural_network::configuration::Configuration;
+use spiking_neural_network::failure::{Components, Failure};
+
+fn create_campaign(seed: u64) -> Campaign {
+    Campaign::new(seed, vec![Components::VTh, Components::Weights, Components::PrevSpikes], 400, 12)
+}
+
+#[test]
+fn verify_same_seed_same_confs() {
+    let c1 = create_campaign(42);
+    let c2 = create_campaign(42);
+
+    assert_eq!(c1.generate_confs(), c2.generate_confs());
+}
+
+#[test]
+fn verify_different_seed_different_confs() {
+    let c1 = create_campaign(1);
+    let c2 = create_campaign(2);
+
+    assert_ne!(c1.generate_confs(), c2.generate_confs());
+}
+
+#[test]
+fn verify_confs_per_component() {
+    let c = create_campaign(7);
+    let confs = c.generate_confs();
+
+    assert_eq!(confs.len(), 9);
+    for site in confs.chunks(3) {
+        /* the three failures share the same component, neuron and bit */
+        assert_eq!(site[0].get_vec_components(), site[1].get_vec_components());
+        assert_eq!(site[0].get_vec_components(), site[2].get_vec_components());
+        assert_eq!(site[0].get_index_neuron(), site[2].get_index_neuron());
+        assert_eq!(site[0].get_failure().get_position(), site[2].get_failure().get_position());
+
+        assert!(matches!(site[0].get_failure(), Failure::StuckAt1(_)));
+        assert!(matches!(site[1].get_failure(), Failure::StuckAt0(_)));
+        assert!(matches!(site[2].get_failure(), Failure::TransientBitFlip(_)));
+
+        assert!(site[0].get_index_neuron() < 400);
+        assert!(site[0].get_failure().get_position().unwrap() < 12);
+    }
+}
+
+#[test]
+fn verify_streams_are_independent() {
+    assert_ne!(derive_seed(42, 0), derive_seed(42, 1));
+    assert_eq!(derive_seed(42, 3), derive_seed(42, 3));
+
+    let mut rng1 = rng_from_seed(42, 5);
+    let mut rng2 = rng_from_seed(42, 5);
+    for _ in 0..8 {
+        assert_eq!(rng1.gen::<u64>(), rng2.gen::<u64>());
+    }
+}
+
+#[test]
+#[should_panic]
+fn verify_campaign_without_neurons() {
+    Campaign::new(0, vec![Components::VTh], 0, 12);
+}
+
+#[test]
+fn verify_campaign_from_spec() {
+    let spec = "# campaign of the example\nseed = 42\ncomponents = VTh, Weights, PrevSpikes\nneurons = 400 # output layer\nbits = 12\n";
+    assert_eq!(Campaign::from_spec(spec).unwrap(), create_campaign(42));
+
+    /* the one line specification of a campaign is a valid specification */
+    let campaign = create_campaign(7);
+    assert_eq!(Campaign::from_spec(&campaign.get_spec()).unwrap(), campaign);
+}
+
+#[test]
+fn verify_invalid_campaign_spec() {
+    assert!(Campaign::from_spec("seed=1 components=VTh neurons=400").is_err());
+    assert!(Campaign::from_spec("seed=1 components=VTh,Spikes neurons=400 bits=12").is_err());
+    assert!(Campaign::from_spec("seed=one components=VTh neurons=400 bits=12").is_err());
+    assert!(Campaign::from_spec("seed=1 components=VTh neurons=400 bits=12 layer=0").is_err());
+    assert!(Campaign::from_spec("seed=1 components=VTh neurons=0 bits=12").is_err());
+}
+
+#[test]
+fn verify_component_names() {
+    for component in [Components::VTh, Components::Weights, Components::IntraWeights, Components::None] {
+        assert_eq!(Components::from_name(component.get_name()), Some(component));
+    }
+    assert_eq!(Components::from_name("Spikes"), None);
+}
@@ -0,0 +1,87 @@
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use spiking_neural_network::campaign::Campaign;
+use spiking_neural_network::checkpoint::Checkpoint;
+use spiking_neural_network::failure::Components;
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("snn_test_checkpoint_{}_{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+const INPUT: &str = "spikes.zip";
+
+fn create_campaign(seed: u64) -> Campaign {
+    Campaign::new(seed, vec![Components::VTh, Components::Weights], 400, 12)
+}
+
+#[test]
+fn verify_campaign_spec() {
+    assert_eq!(create_campaign(3).get_spec(), "campaign seed=3 components=VTh,Weights neurons=400 bits=12");
+    assert_ne!(create_campaign(3).get_spec(), create_campaign(4).get_spec());
+    assert_eq!(create_campaign(3).get_run_spec(INPUT, 51),
+               "campaign seed=3 components=VTh,Weights neurons=400 bits=12 input=spikes.zip samples=51");
+}
+
+#[test]
+fn verify_checkpoint_resume() {
+    let path = temp_path("resume.txt");
+    let campaign = create_campaign(3);
+
+    let mut checkpoint = Checkpoint::open(&path, &campaign, INPUT, 51).unwrap();
+    assert_eq!(checkpoint.get_num_done(), 0);
+    checkpoint.mark_done(0).unwrap();
+    checkpoint.mark_done(4).unwrap();
+    checkpoint.mark_done(4).unwrap();
+    drop(checkpoint);
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "campaign seed=3 components=VTh,Weights neurons=400 bits=12 input=spikes.zip samples=51\n0\n4\n");
+
+    /* an interrupted run leaves an incomplete line */
+    OpenOptions::new().append(true).open(&path).unwrap().write_all(b"5").unwrap();
+
+    let mut checkpoint = Checkpoint::open(&path, &campaign, INPUT, 51).unwrap();
+    assert_eq!(checkpoint.get_num_done(), 2);
+    let done: Vec<usize> = (0..campaign.generate_confs().len()).filter(|id| checkpoint.is_done(*id)).collect();
+    assert_eq!(done, vec![0, 4]);
+
+    checkpoint.mark_done(5).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "campaign seed=3 components=VTh,Weights neurons=400 bits=12 input=spikes.zip samples=51\n0\n4\n5\n");
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn verify_checkpoint_other_campaign() {
+    let path = temp_path("other.txt");
+
+    let mut checkpoint = Checkpoint::open(&path, &create_campaign(3), INPUT, 51).unwrap();
+    checkpoint.mark_done(1).unwrap();
+    drop(checkpoint);
+
+    let error = Checkpoint::open(&path, &create_campaign(4), INPUT, 51).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+    /* same campaign on another dataset */
+    let error = Checkpoint::open(&path, &create_campaign(3), INPUT, 10).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    let error = Checkpoint::open(&path, &create_campaign(3), "other.zip", 51).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert!(Checkpoint::open(&path, &create_campaign(3), INPUT, 51).is_ok());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn verify_checkpoint_invalid_id() {
+    let path = temp_path("invalid.txt");
+    fs::write(&path, "campaign seed=3 components=VTh,Weights neurons=400 bits=12 input=spikes.zip samples=51\nVTh\n").unwrap();
+
+    let error = Checkpoint::open(&path, &create_campaign(3), INPUT, 51).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,39 @@
+use spiking_neural_network::evaluation::{accuracy, classify};
+use spiking_neural_network::failure::{Conf, Failure};
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::snn::builder::SnnBuilder;
+
+#[test]
+fn verify_classify() {
+    let output_spikes = vec![
+        vec![1, 1, 0],  /* label 0 */
+        vec![1, 0, 0],  /* label 1 */
+        vec![1, 0, 1],  /* label 1 */
+    ];
+    assert_eq!(classify(&output_spikes, &[0, 1, 1]), Some(1));
+    assert_eq!(classify(&output_spikes, &[0, 2, 1]), Some(0));
+}
+
+#[test]
+fn verify_classify_no_spikes() {
+    assert_eq!(classify(&[vec![0, 0], vec![0, 0]], &[0, 1]), None);
+}
+
+#[test]
+fn verify_accuracy() {
+    /* each neuron only listens to its own input */
+    let mut snn = SnnBuilder::<LifNeuron, Conf>::new(2)
+        .add_layer(vec![LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0); 2],
+                   vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+                   vec![vec![0.0, 0.0], vec![0.0, 0.0]],
+                   Conf::new(vec![], Failure::None, 0))
+        .build();
+
+    let inputs = vec![
+        vec![vec![1, 1], vec![0, 0]],
+        vec![vec![0, 0], vec![1, 0]],
+        vec![vec![0, 1], vec![0, 0]],
+        vec![vec![0, 0], vec![0, 0]],
+    ];
+    assert_eq!(accuracy(&mut snn, &inputs, &[0, 1, 1, 0], &[0, 1]), 50.0);
+}
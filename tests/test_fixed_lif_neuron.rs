@@ -0,0 +1,112 @@
+use std::sync::mpsc::channel;
+use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt1, TransientBitFlip};
+use spiking_neural_network::fixed_lif_neuron::FixedLifNeuron;
+use spiking_neural_network::fixed_point::FixedFormat;
+use spiking_neural_network::layer::{Layer, modify_bits_width};
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::neuron::Neuron;
+use spiking_neural_network::spike_event::SpikeEvent;
+
+fn run<N: Neuron + Clone + Send + 'static>(layer: &mut Layer<N, Conf>) {
+    let (input_tx, input_rc) = channel();
+    let (output_tx, _output_rc) = channel();
+    input_tx.send(SpikeEvent::new(0, vec![1, 1])).unwrap();
+    drop(input_tx);
+    layer.process(input_rc, output_tx);
+}
+
+#[test]
+fn verify_format_conversions() {
+    let format = FixedFormat::new(8, 8);
+
+    assert_eq!(format.get_width(), 16);
+    assert_eq!(format.to_raw(1.5), 384);
+    assert_eq!(format.from_raw(384), 1.5);
+    assert_eq!(format.to_word(-1.0), 0xFF00);
+    assert_eq!(format.from_word(0xFF00), -1.0);
+    assert_eq!(format.quantize(0.1), 26.0 / 256.0);
+
+    /* saturation */
+    assert_eq!(format.to_raw(1000.0), 32767);
+    assert_eq!(format.to_raw(-1000.0), -32768);
+    assert_eq!(format.saturating_add(32767, 1), 32767);
+}
+
+#[test]
+fn verify_spike_and_reset() {
+    let mut n = FixedLifNeuron::new(1.0, 0.0, 0.25, 10.0, 1.0, FixedFormat::new(8, 8));
+
+    assert_eq!(n.calculate_v_mem(1, 0.5), 0);
+    assert_eq!(n.get_v_mem(), 0.5);
+    assert_eq!(n.calculate_v_mem(1, 0.75), 1);
+    assert_eq!(n.get_v_mem(), 0.25);
+
+    n.init();
+    assert_eq!(n.get_v_mem(), 0.0);
+    assert_eq!(n.get_ts(), 0);
+}
+
+#[test]
+fn verify_shift_leak() {
+    /* tau / dt = 8 -> v_mem -= (v_mem - v_rest) >> 3 for each instant */
+    let mut n = FixedLifNeuron::new(100.0, 0.0, 0.0, 8.0, 1.0, FixedFormat::new(8, 8));
+
+    n.calculate_v_mem(0, 64.0);
+    n.calculate_v_mem(2, 0.0);
+    assert_eq!(n.get_v_mem(), 49.0);
+
+    /* without leak the potential is kept */
+    let mut n = FixedLifNeuron::new(100.0, 0.0, 0.0, 0.0, 1.0, FixedFormat::new(8, 8));
+    n.calculate_v_mem(0, 64.0);
+    n.calculate_v_mem(5, 0.0);
+    assert_eq!(n.get_v_mem(), 64.0);
+}
+
+#[test]
+fn verify_saturating_sum() {
+    let mut n = FixedLifNeuron::new(127.0, 0.0, 0.0, 0.0, 1.0, FixedFormat::new(8, 8));
+
+    n.calculate_v_mem(0, 100.0);
+    assert_eq!(n.calculate_v_mem(0, 100.0), 1);
+    n.set_v_mem(126.0);
+    n.calculate_v_mem(0, 10.0);
+    assert_eq!(n.get_v_mem(), 0.0);
+}
+
+#[test]
+fn verify_modify_bits_width() {
+    let failure = Failure::StuckAt1(StuckAt1::new(0));
+    assert_eq!(modify_bits_width(failure.clone(), 0x0100, 16), 0x8100);
+    /* positions are taken modulo the width */
+    assert_eq!(modify_bits_width(failure, 0x0100, 16), modify_bits_width(Failure::StuckAt1(StuckAt1::new(16)), 0x0100, 16));
+}
+
+#[test]
+fn verify_fault_on_fixed_word() {
+    let format = FixedFormat::new(8, 8);
+    let neurons = vec![FixedLifNeuron::new(1.0, 0.0, 0.25, 10.0, 1.0, format); 2];
+
+    /* bit 0 is the sign bit of the 16 bit word */
+    let configuration = Conf::new(vec![Components::VTh], Failure::StuckAt1(StuckAt1::new(0)), 1);
+    let mut layer = Layer::new(neurons, vec![vec![0.1, 0.2], vec![0.3, 0.4]], vec![vec![0.0; 2]; 2], configuration);
+    run(&mut layer);
+
+    assert_eq!(layer.get_neurons()[1].get_v_th(), -127.0);
+    assert_eq!(layer.get_neurons()[0].get_v_th(), 1.0);
+}
+
+#[test]
+fn verify_fixed_point_weights() {
+    let format = FixedFormat::new(4, 12);
+    let neurons = vec![LifNeuron::new(0.9, 0.33, 0.14, 0.4, 0.05); 2];
+
+    /* bit 17 is the bit 1 of the second word of the matrix: weights[0][1] */
+    let configuration = Conf::new(vec![Components::Weights], Failure::TransientBitFlip(TransientBitFlip::new(17)), 0);
+    let mut layer = Layer::new(neurons, vec![vec![0.1, 0.2], vec![0.3, 0.4]], vec![vec![0.0; 2]; 2], configuration);
+    layer.enable_fixed_point_weights(format);
+
+    assert_eq!(layer.get_weights()[0][0], format.quantize(0.1));
+    run(&mut layer);
+
+    assert_eq!(layer.get_weights()[0][1], format.quantize(0.2) + 4.0);
+}
@@ -0,0 +1,74 @@
+use std::sync::mpsc::channel;
+use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt1, TransientBitFlip};
+use spiking_neural_network::float::Float;
+use spiking_neural_network::layer::Layer;
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::neuron::Neuron;
+use spiking_neural_network::snn::builder::SnnBuilder;
+use spiking_neural_network::spike_event::SpikeEvent;
+
+fn create_layer<F: Float>(configuration: Conf) -> Layer<LifNeuron<F>, Conf> {
+    let f = |val: f64| F::from_f64(val);
+    let neurons = vec![
+        LifNeuron::new(f(0.76), f(0.33), f(0.14), f(0.4), f(0.05)),
+        LifNeuron::new(f(0.88), f(0.3), f(0.1), f(0.2), f(0.05)),
+        LifNeuron::new(f(0.9), f(0.2), f(0.05), f(0.1), f(0.05)),
+    ];
+    let weights = vec![vec![f(0.1), f(0.2)], vec![f(0.3), f(0.4)], vec![f(0.5), f(0.6)]];
+    let intra_weights = vec![
+        vec![f(0.0), f(-0.5), f(-0.15)],
+        vec![f(-0.05), f(0.0), f(-0.2)],
+        vec![f(-0.35), f(-0.1), f(0.0)],
+    ];
+    Layer::new(neurons, weights, intra_weights, configuration)
+}
+
+fn run<F: Float>(layer: &mut Layer<LifNeuron<F>, Conf>) {
+    let (input_tx, input_rc) = channel();
+    let (output_tx, _output_rc) = channel();
+    input_tx.send(SpikeEvent::new(0, vec![1, 1])).unwrap();
+    drop(input_tx);
+    layer.process(input_rc, output_tx);
+}
+
+#[test]
+fn verify_word_width() {
+    assert_eq!(<f32 as Float>::WIDTH, 32);
+    assert_eq!(<f64 as Float>::WIDTH, 64);
+    assert_eq!(1.5f32.to_word(), 0x3FC0_0000);
+    assert_eq!(<f32 as Float>::from_word(0x3FC0_0000), 1.5f32);
+    assert_eq!(LifNeuron::new(0.5f32, 0.0, 0.0, 1.0, 1.0).get_word_width(), 32);
+}
+
+#[test]
+fn verify_f32_same_output_as_f64() {
+    let input_spikes = vec![
+        vec![0, 1, 1, 0, 1],
+        vec![1, 0, 1, 1, 1],
+    ];
+
+    let l64 = create_layer::<f64>(Conf::new(vec![], Failure::None, 0));
+    let l32 = create_layer::<f32>(Conf::new(vec![], Failure::None, 0));
+
+    let mut snn64 = SnnBuilder::<LifNeuron<f64>, Conf>::new(2)
+        .add_layer(l64.get_neurons(), l64.get_weights(), l64.get_intra_weights(), Conf::new(vec![], Failure::None, 0))
+        .build();
+    let mut snn32 = SnnBuilder::<LifNeuron<f32>, Conf>::new(2)
+        .add_layer(l32.get_neurons(), l32.get_weights(), l32.get_intra_weights(), Conf::new(vec![], Failure::None, 0))
+        .build();
+
+    assert_eq!(snn32.process(&input_spikes), snn64.process(&input_spikes));
+}
+
+#[test]
+fn verify_f32_fault_on_32_bit_word() {
+    /* bit 1 is the most significant bit of the exponent of the 32 bit word */
+    let mut layer = create_layer::<f32>(Conf::new(vec![Components::VTh], Failure::StuckAt1(StuckAt1::new(1)), 0));
+    run(&mut layer);
+    assert_eq!(layer.get_neurons()[0].get_v_th(), f32::from_bits(0.76f32.to_bits() | (1 << 30)));
+
+    /* bit 33 is the bit 1 of the second word of the matrix: weights[0][1] */
+    let mut layer = create_layer::<f32>(Conf::new(vec![Components::Weights], Failure::TransientBitFlip(TransientBitFlip::new(33)), 0));
+    run(&mut layer);
+    assert_eq!(layer.get_weights()[0][1], f32::from_bits(0.2f32.to_bits() ^ (1 << 30)));
+}
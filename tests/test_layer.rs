@@ -516,7 +516,7 @@ fn verify_modification_bit_prev_spikes_stuck0() {
 
     l.set_prev_spikes(modified_prev_spikes);
 
-    assert_eq!(l.get_prev_spikes(), vec![0,1,0]);
+    assert_eq!(l.get_prev_spikes().to_vec(), vec![0,1,0]);
 }
 
 
@@ -530,7 +530,7 @@ fn verify_modification_bit_prev_spikes_stuck1() {
 
     l.set_prev_spikes(modified_prev_spikes);
 
-    assert_eq!(l.get_prev_spikes(), vec![1,1,1]);
+    assert_eq!(l.get_prev_spikes().to_vec(), vec![1,1,1]);
 }
 
 #[test]
@@ -543,7 +543,71 @@ fn verify_modification_bit_prev_spikes_transient() {
 
     l.set_prev_spikes(modified_prev_spikes);
 
-    assert_eq!(l.get_prev_spikes(), vec![1,1,1]);
+    assert_eq!(l.get_prev_spikes().to_vec(), vec![1,1,1]);
 }
 
 
+
+#[test]
+fn verify_fault_effective_stuck_at() {
+    /* position 0 is the sign bit, which is 0 for a positive v_th */
+    let l = create_layer(Conf::new(vec![Components::VTh], Failure::StuckAt0(StuckAt0::new(0)), 1));
+    assert!(!l.is_fault_effective(100));
+
+    let l = create_layer(Conf::new(vec![Components::VTh], Failure::StuckAt1(StuckAt1::new(0)), 1));
+    assert!(l.is_fault_effective(100));
+
+    let l = create_layer(Conf::new(vec![Components::Weights], Failure::StuckAt0(StuckAt0::new(0)), 1));
+    assert!(!l.is_fault_effective(100));
+}
+
+#[test]
+fn verify_fault_effective_transient_and_none() {
+    let l = create_layer(Conf::new(vec![Components::VTh], Failure::TransientBitFlip(TransientBitFlip::new(0)), 1));
+    assert!(l.is_fault_effective(100));
+
+    let l = create_layer(Conf::new(vec![Components::VTh], Failure::None, 1));
+    assert!(!l.is_fault_effective(100));
+}
+
+#[test]
+fn verify_fault_effective_time_dependent() {
+    let l = create_layer(Conf::new(vec![Components::Ts], Failure::StuckAt0(StuckAt0::new(63)), 1));
+    assert!(l.is_fault_effective(100));
+
+    /* bit 57 is worth 64: only the samples longer than 64 instants reach it */
+    let l = create_layer(Conf::new(vec![Components::Ts], Failure::StuckAt0(StuckAt0::new(57)), 1));
+    assert!(!l.is_fault_effective(64));
+    assert!(l.is_fault_effective(65));
+
+    /* the first instant is 0, so a StuckAt1 always changes it */
+    let l = create_layer(Conf::new(vec![Components::Ts], Failure::StuckAt1(StuckAt1::new(0)), 1));
+    assert!(l.is_fault_effective(1));
+
+    let l = create_layer(Conf::new(vec![Components::PrevSpikes], Failure::StuckAt1(StuckAt1::new(1)), 1));
+    assert!(l.is_fault_effective(100));
+
+    /* without intra weights the previous spikes are never read */
+    let n = LifNeuron::new(0.9, 0.33, 0.14, 0.4, 0.05);
+    let l = Layer::new(vec![n; 2], vec![vec![0.1], vec![0.2]], vec![vec![0.0; 2]; 2],
+                       Conf::new(vec![Components::PrevSpikes], Failure::StuckAt1(StuckAt1::new(1)), 1));
+    assert!(!l.is_fault_effective(100));
+
+    /* without leak the last instant is never read */
+    let n = LifNeuron::new(0.9, 0.33, 0.14, 0.0, 0.05);
+    let l = Layer::new(vec![n; 2], vec![vec![0.1], vec![0.2]], vec![vec![0.0; 2]; 2],
+                       Conf::new(vec![Components::Ts], Failure::StuckAt1(StuckAt1::new(63)), 1));
+    assert!(!l.is_fault_effective(100));
+}
+
+#[test]
+fn verify_fault_weight_position() {
+    let l = create_layer(Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(64 * 4 + 1)), 1));
+    assert_eq!(l.get_fault_weight_position(), Some((1, 1)));
+
+    let l = create_layer(Conf::new(vec![Components::IntraWeights], Failure::StuckAt0(StuckAt0::new(64 * 5)), 1));
+    assert_eq!(l.get_fault_weight_position(), Some((1, 2)));
+
+    let l = create_layer(Conf::new(vec![Components::VTh], Failure::StuckAt1(StuckAt1::new(3)), 1));
+    assert_eq!(l.get_fault_weight_position(), None);
+}
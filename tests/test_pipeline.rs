@@ -0,0 +1,84 @@
+use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt1};
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::network::SNN;
+use spiking_neural_network::pipeline::Pipeline;
+use spiking_neural_network::snn::builder::SnnBuilder;
+
+fn create_network(configuration: Conf) -> SNN<LifNeuron, Conf> {
+    SnnBuilder::<LifNeuron, Conf>::new(3)
+        .add_layer(vec![LifNeuron::new(0.6, 0.0, 0.1, 1.0, 0.5); 3],
+                   vec![vec![0.5, 0.3, 0.0], vec![0.1, 0.4, 0.4], vec![0.0, 0.2, 0.7]],
+                   vec![vec![0.0, -0.1, -0.2], vec![-0.1, 0.0, -0.1], vec![-0.2, -0.1, 0.0]],
+                   configuration)
+        .add_layer(vec![LifNeuron::new(0.3, 0.0, 0.0, 1.0, 0.5); 2],
+                   vec![vec![0.4, 0.1, 0.2], vec![0.0, 0.3, 0.5]],
+                   vec![vec![0.0, -0.05], vec![-0.05, 0.0]],
+                   Conf::new(vec![], Failure::None, 0))
+        .add_layer(vec![LifNeuron::new(0.2, 0.0, 0.0, 1.0, 0.5); 2],
+                   vec![vec![0.3, 0.0], vec![0.1, 0.3]],
+                   vec![vec![0.0, 0.0], vec![0.0, 0.0]],
+                   Conf::new(vec![], Failure::None, 0))
+        .build()
+}
+
+fn create_samples() -> Vec<Vec<Vec<u8>>> {
+    vec![
+        vec![vec![1, 0, 1, 1, 0], vec![0, 1, 1, 0, 0], vec![1, 1, 0, 0, 1]],
+        vec![vec![0, 0, 0], vec![0, 0, 0], vec![0, 0, 0]],
+        vec![vec![1, 1, 1, 1], vec![0, 0, 0, 0], vec![1, 0, 1, 0]],
+        vec![vec![0, 1], vec![1, 1], vec![1, 0]],
+    ]
+}
+
+#[test]
+fn verify_same_output_as_batch() {
+    let samples = create_samples();
+    let configuration = Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(0)), 0);
+    let expected = create_network(configuration.clone()).process_batch(&samples);
+
+    let mut pipeline = Pipeline::new(create_network(configuration));
+    assert_eq!(pipeline.process(&samples), expected);
+    /* the pipeline keeps running across calls */
+    assert_eq!(pipeline.process(&samples), expected);
+}
+
+#[test]
+fn verify_submit_receive() {
+    let samples = create_samples();
+    let expected = create_network(Conf::new(vec![], Failure::None, 0)).process_batch(&samples);
+
+    let mut pipeline = Pipeline::new(create_network(Conf::new(vec![], Failure::None, 0)));
+    assert_eq!(pipeline.receive(), None);
+
+    pipeline.submit(&samples[0]);
+    pipeline.submit(&samples[1]);
+    assert_eq!(pipeline.get_pending(), 2);
+    assert_eq!(pipeline.receive(), Some(expected[0].clone()));
+
+    pipeline.submit(&samples[2]);
+    pipeline.submit(&samples[3]);
+    for output in expected.iter().skip(1) {
+        assert_eq!(pipeline.receive().as_ref(), Some(output));
+    }
+    assert_eq!(pipeline.get_pending(), 0);
+}
+
+#[test]
+fn verify_finish() {
+    let samples = create_samples();
+    let mut pipeline = Pipeline::new(create_network(Conf::new(vec![], Failure::None, 0)));
+    /* outputs never received are discarded */
+    pipeline.submit(&samples[0]);
+
+    let mut snn = pipeline.finish();
+    assert_eq!(snn.process(&samples[0]), create_network(Conf::new(vec![], Failure::None, 0)).process(&samples[0]));
+}
+
+#[test]
+#[should_panic(expected = "The outputs of the samples already submitted must be received first")]
+fn verify_process_with_pending() {
+    let samples = create_samples();
+    let mut pipeline = Pipeline::new(create_network(Conf::new(vec![], Failure::None, 0)));
+    pipeline.submit(&samples[0]);
+    pipeline.process(&samples);
+}
@@ -0,0 +1,86 @@
+use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt1};
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::network::SNN;
+use spiking_neural_network::probe::{Probe, ProbeSample};
+use spiking_neural_network::snn::builder::SnnBuilder;
+
+/* without leak (tau = 0) the neuron integrates 0.4 for each input spike */
+fn create_network(configuration: Conf) -> SNN<LifNeuron, Conf> {
+    SnnBuilder::<LifNeuron, Conf>::new(1)
+        .add_layer(vec![LifNeuron::new(1.0, 0.0, 0.0, 0.0, 1.0); 2], vec![vec![0.4], vec![0.1]],
+                   vec![vec![0.0, 0.0], vec![-0.05, 0.0]], configuration)
+        .add_layer(vec![LifNeuron::new(0.5, 0.0, 0.0, 0.0, 1.0)], vec![vec![0.6, 0.0]], vec![vec![0.0]],
+                   Conf::new(vec![], Failure::None, 0))
+        .build()
+}
+
+#[test]
+fn verify_probe_time_series() {
+    let mut snn = create_network(Conf::new(vec![], Failure::None, 0));
+    snn.add_probe(0, 0);
+    snn.add_probe(1, 0);
+
+    let (output, probes) = snn.process_probed(&vec![vec![1, 1, 0, 1, 1, 1]]);
+    assert_eq!(output, vec![vec![0, 0, 0, 1, 0, 0]]);
+    assert_eq!(probes.len(), 2);
+
+    /* the instant 2 has no input spikes and is skipped */
+    let probe = &probes[0];
+    assert_eq!((probe.get_layer(), probe.get_neuron()), (0, 0));
+    let instants: Vec<u64> = probe.get_samples().iter().map(|sample| sample.instant).collect();
+    assert_eq!(instants, vec![0, 1, 3, 4, 5]);
+    let v_mem: Vec<f64> = probe.get_v_mem().into_iter().map(|(_, v_mem)| v_mem).collect();
+    assert_eq!(v_mem, vec![0.4, 0.8, 0.0, 0.4, 0.8]);
+    let spikes: Vec<u8> = probe.get_samples().iter().map(|sample| sample.spike).collect();
+    assert_eq!(spikes, vec![0, 0, 1, 0, 0]);
+
+    /* the second layer only receives the spike of the instant 3 */
+    assert_eq!(probes[1].get_samples(), &[ProbeSample { instant: 3, v_mem: 0.0, ts: 3, v_th: 0.5, extra_sum: 0.6, intra_sum: 0.0, spike: 1 }]);
+}
+
+#[test]
+fn verify_probe_intra_sum() {
+    let mut snn = create_network(Conf::new(vec![], Failure::None, 0));
+    snn.add_probe(0, 1);
+    snn.process(&vec![vec![1, 1, 1, 1]]);
+
+    /* the neuron 0 fires at the instant 2, the neuron 1 receives its intra weight at the instant 3 */
+    let intra_sums: Vec<f64> = snn.get_probes()[0].get_samples().iter().map(|sample| sample.intra_sum).collect();
+    assert_eq!(intra_sums, vec![0.0, 0.0, 0.0, -0.05]);
+}
+
+#[test]
+fn verify_probe_shows_fault() {
+    /* bit 1 of v_th (exponent MSB): the threshold becomes huge and the neuron never fires */
+    let mut snn = create_network(Conf::new(vec![Components::VTh], Failure::StuckAt1(StuckAt1::new(1)), 0));
+    snn.add_probe(0, 0);
+
+    let (output, probes) = snn.process_probed(&vec![vec![1, 1, 1, 1]]);
+    assert_eq!(output, vec![vec![0; 4]]);
+    assert!(probes[0].get_samples().iter().all(|sample| sample.v_th > 1e300));
+    assert!((probes[0].get_samples()[3].v_mem - 1.6).abs() < 1e-12);
+}
+
+#[test]
+fn verify_probe_cleared_on_reset() {
+    let mut snn = create_network(Conf::new(vec![], Failure::None, 0));
+    snn.add_probe(0, 0);
+    snn.process(&vec![vec![1, 1]]);
+    snn.process(&vec![vec![1, 1, 1]]);
+    assert_eq!(snn.get_probes()[0].get_samples().len(), 3);
+
+    snn.reset();
+    assert!(snn.get_probes()[0].get_samples().is_empty());
+    snn.step(&[1]);
+    assert_eq!(snn.get_probes()[0].get_v_mem(), vec![(0, 0.4)]);
+
+    let mut probe = Probe::<f64>::new(0, 0);
+    probe.clear();
+    assert!(probe.get_samples().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "The probed neuron 2 does not exist in a layer of 2 neurons")]
+fn verify_probe_wrong_neuron() {
+    create_network(Conf::new(vec![], Failure::None, 0)).add_probe(0, 2);
+}
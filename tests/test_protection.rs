@@ -0,0 +1,95 @@
+use std::sync::mpsc::channel;
+use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt0, StuckAt1, TransientBitFlip};
+use spiking_neural_network::layer::Layer;
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::protection::{ProtectedWeights, WeightProtection};
+use spiking_neural_network::spike_event::SpikeEvent;
+
+fn create_layer(configuration: Conf, protection: WeightProtection) -> Layer<LifNeuron, Conf> {
+    let neurons = vec![LifNeuron::new(0.9, 0.33, 0.14, 0.4, 0.05); 2];
+    let weights = vec![vec![0.1, 0.2], vec![0.3, 0.4]];
+    let intra_weights = vec![vec![0.0, -0.1], vec![-0.2, 0.0]];
+    let mut layer = Layer::new(neurons, weights, intra_weights, configuration);
+    layer.enable_weights_protection(protection);
+    layer
+}
+
+fn run(layer: &mut Layer<LifNeuron, Conf>) {
+    let (input_tx, input_rc) = channel();
+    let (output_tx, _output_rc) = channel();
+    input_tx.send(SpikeEvent::new(0, vec![1, 1])).unwrap();
+    drop(input_tx);
+    layer.process(input_rc, output_tx);
+}
+
+#[test]
+fn verify_parity_zeroes_weight() {
+    let mut layer = create_layer(Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(1)), 0),
+                                 WeightProtection::Parity);
+    run(&mut layer);
+
+    assert_eq!(layer.get_weights()[0][0], 0.0);
+    assert_eq!(layer.get_weights_protection().unwrap().get_detected(), 1);
+}
+
+#[test]
+fn verify_sec_ded_corrects_single_error() {
+    let mut layer = create_layer(Conf::new(vec![Components::IntraWeights], Failure::TransientBitFlip(TransientBitFlip::new(1)), 0),
+                                 WeightProtection::SecDed);
+    run(&mut layer);
+
+    assert_eq!(layer.get_intra_weights()[0][0], 0.0);
+    let protection = layer.get_intra_weights_protection().unwrap();
+    assert_eq!(protection.get_corrected(), 1);
+    assert_eq!(protection.get_detected(), 0);
+}
+
+#[test]
+fn verify_counters_reset_by_init() {
+    let mut layer = create_layer(Conf::new(vec![Components::IntraWeights], Failure::TransientBitFlip(TransientBitFlip::new(1)), 0),
+                                 WeightProtection::SecDed);
+    run(&mut layer);
+    assert_eq!(layer.get_intra_weights_protection().unwrap().get_corrected(), 1);
+
+    /* a new run starts counting again */
+    layer.init();
+    let protection = layer.get_intra_weights_protection().unwrap();
+    assert_eq!(protection.get_corrected() + protection.get_detected() + protection.get_undetected(), 0);
+}
+
+#[test]
+fn verify_masked_stuck_at_not_counted() {
+    /* the sign bit of a positive weight is already 0 */
+    let mut layer = create_layer(Conf::new(vec![Components::Weights], Failure::StuckAt0(StuckAt0::new(0)), 0),
+                                 WeightProtection::SecDed);
+    run(&mut layer);
+
+    let protection = layer.get_weights_protection().unwrap();
+    assert_eq!(layer.get_weights()[0][0], 0.1);
+    assert_eq!(protection.get_corrected() + protection.get_detected() + protection.get_undetected(), 0);
+}
+
+#[test]
+fn verify_double_error() {
+    let word = 0.5f64.to_bits();
+    let double = word ^ 0b101;
+
+    let mut sec_ded = ProtectedWeights::new(WeightProtection::SecDed, &[vec![word]]);
+    assert_eq!(sec_ded.read(0, 0, word, double), None);
+    assert_eq!(sec_ded.get_detected(), 1);
+
+    /* an even number of errors is not seen by the parity */
+    let mut parity = ProtectedWeights::new(WeightProtection::Parity, &[vec![word]]);
+    assert_eq!(parity.read(0, 0, word, double), Some(double));
+    assert_eq!(parity.get_undetected(), 1);
+}
+
+#[test]
+fn verify_sec_ded_every_bit() {
+    let word = (-0.37f64).to_bits();
+    for k in 0..64 {
+        let mut sec_ded = ProtectedWeights::new(WeightProtection::SecDed, &[vec![word]]);
+        assert_eq!(sec_ded.read(0, 0, word, word ^ (1u64 << k)), Some(word));
+        assert_eq!(sec_ded.get_corrected(), 1);
+    }
+}
@@ -0,0 +1,66 @@
+use spiking_neural_network::failure::{Conf, Failure};
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::quantization::{format_table, quantization_sweep, quantize_weights, QuantizationResult, QuantizationScheme};
+use spiking_neural_network::snn::builder::SnnBuilder;
+
+#[test]
+fn verify_uniform_positive() {
+    /* 2 bits and positive weights: levels 0, 0.3, 0.6, 0.9 */
+    let weights: Vec<Vec<f64>> = vec![vec![0.0, 0.2, 0.5], vec![0.9, 0.7, 0.1]];
+    let quantized = quantize_weights(&weights, 2, QuantizationScheme::Uniform);
+
+    let expected = [[0.0, 0.3, 0.6], [0.9, 0.6, 0.0]];
+    for (row, expected_row) in quantized.iter().zip(expected.iter()) {
+        for (q, e) in row.iter().zip(expected_row.iter()) {
+            assert!((q - e).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn verify_uniform_mixed_signs() {
+    /* 3 bits with sign: levels k * 0.25 for k in [-3, 3] */
+    let weights = vec![vec![-0.75, 0.0, 0.3, 0.74]];
+    assert_eq!(quantize_weights(&weights, 3, QuantizationScheme::Uniform), vec![vec![-0.75, 0.0, 0.25, 0.75]]);
+}
+
+#[test]
+fn verify_per_row() {
+    let weights = vec![vec![0.0, 1.0], vec![0.0, 0.01, 0.02]];
+
+    let uniform = quantize_weights(&weights, 1, QuantizationScheme::Uniform);
+    assert_eq!(uniform[1], vec![0.0, 0.0, 0.0]);
+
+    /* each row keeps its own scale */
+    let per_row = quantize_weights(&weights, 1, QuantizationScheme::PerRow);
+    assert_eq!(per_row[0], vec![0.0, 1.0]);
+    assert_eq!(per_row[1], vec![0.0, 0.02, 0.02]);
+}
+
+#[test]
+fn verify_zero_weights() {
+    let weights = vec![vec![0.0f32; 3]];
+    assert_eq!(quantize_weights(&weights, 4, QuantizationScheme::Uniform), weights);
+}
+
+#[test]
+fn verify_sweep() {
+    let builder = SnnBuilder::<LifNeuron, Conf>::new(2)
+        .add_layer(vec![LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0); 2],
+                   vec![vec![1.0, 0.3], vec![0.3, 1.0]],
+                   vec![vec![0.0, -0.2], vec![-0.2, 0.0]],
+                   Conf::new(vec![], Failure::None, 0));
+
+    let inputs = vec![vec![vec![1, 1], vec![0, 0]], vec![vec![0, 0], vec![1, 1]]];
+    let results = quantization_sweep(&builder, 1..=4, QuantizationScheme::PerRow, &inputs, &[0, 1], &[0, 1]);
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[3], QuantizationResult { bits: 4, accuracy: 100.0 });
+    assert!(format_table(&results).starts_with("bits,accuracy\n1,"));
+}
+
+#[test]
+#[should_panic]
+fn verify_zero_bits() {
+    quantize_weights(&[vec![0.5]], 0, QuantizationScheme::Uniform);
+}
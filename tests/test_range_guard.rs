@@ -0,0 +1,111 @@
+use std::sync::mpsc::channel;
+use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt1};
+use spiking_neural_network::layer::Layer;
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::range_guard::{GuardAction, RangeGuard};
+use spiking_neural_network::spike_event::SpikeEvent;
+
+fn create_layer(configuration: Conf) -> Layer<LifNeuron, Conf> {
+    let neurons = vec![
+        LifNeuron::new(0.9, 0.33, 0.14, 0.4, 0.05),
+        LifNeuron::new(0.7, 0.33, 0.14, 0.4, 0.05),
+    ];
+    let weights = vec![vec![0.1, 0.2], vec![0.3, 0.4]];
+    let intra_weights = vec![vec![0.0, -0.1], vec![-0.2, 0.0]];
+    Layer::new(neurons, weights, intra_weights, configuration)
+}
+
+fn run(layer: &mut Layer<LifNeuron, Conf>) {
+    let (input_tx, input_rc) = channel();
+    let (output_tx, _output_rc) = channel();
+    input_tx.send(SpikeEvent::new(0, vec![1, 1])).unwrap();
+    input_tx.send(SpikeEvent::new(1, vec![0, 1])).unwrap();
+    drop(input_tx);
+    layer.process(input_rc, output_tx);
+}
+
+#[test]
+fn verify_ranges_from_golden() {
+    let layer = create_layer(Conf::new(vec![], Failure::None, 0));
+    let guard = RangeGuard::new(&layer.get_neurons(), &layer.get_weights(), &layer.get_intra_weights(), 0.0, GuardAction::Clamp);
+
+    assert_eq!(guard.get_weights_range(), (0.1, 0.4));
+    assert_eq!(guard.get_intra_weights_range(), (-0.2, 0.0));
+    assert_eq!(guard.get_detected(), 0);
+}
+
+#[test]
+fn verify_no_detection_without_faults() {
+    let mut layer = create_layer(Conf::new(vec![], Failure::None, 0));
+    layer.enable_range_guard(0.0, GuardAction::Restore);
+    run(&mut layer);
+
+    let guard = layer.get_range_guard().unwrap();
+    assert_eq!(guard.get_detected(), 0);
+    assert_eq!(guard.get_corrected(), 0);
+}
+
+#[test]
+fn verify_restore_v_th() {
+    /* the most significant bit of the exponent makes v_th huge */
+    let mut layer = create_layer(Conf::new(vec![Components::VTh], Failure::StuckAt1(StuckAt1::new(1)), 1));
+    layer.enable_range_guard(0.1, GuardAction::Restore);
+    run(&mut layer);
+
+    let guard = layer.get_range_guard().unwrap();
+    assert_eq!(guard.get_detected(), 1);
+    assert_eq!(guard.get_corrected(), 1);
+    assert_eq!(layer.get_neurons()[1].get_v_th(), 0.7);
+}
+
+#[test]
+fn verify_clamp_weight() {
+    let mut layer = create_layer(Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(1)), 0));
+    layer.enable_range_guard(0.0, GuardAction::Clamp);
+    run(&mut layer);
+
+    /* clamped to the upper bound, which is not the golden value */
+    let guard = layer.get_range_guard().unwrap();
+    assert_eq!(guard.get_detected(), 1);
+    assert_eq!(guard.get_corrected(), 0);
+    assert_eq!(layer.get_weights()[0][0], 0.4);
+}
+
+#[test]
+fn verify_clamp_same_parameters() {
+    /* all the neurons share tau, so the range collapses on the golden value */
+    let mut layer = create_layer(Conf::new(vec![Components::Tau], Failure::StuckAt1(StuckAt1::new(1)), 0));
+    layer.enable_range_guard(0.0, GuardAction::Clamp);
+    run(&mut layer);
+
+    let guard = layer.get_range_guard().unwrap();
+    assert_eq!(guard.get_detected(), 1);
+    assert_eq!(guard.get_corrected(), 1);
+    assert_eq!(layer.get_neurons()[0].get_tau(), 0.4);
+}
+
+#[test]
+fn verify_check_only_faulty_word() {
+    let layer = create_layer(Conf::new(vec![], Failure::None, 0));
+    let mut guard = RangeGuard::new(&layer.get_neurons(), &layer.get_weights(), &layer.get_intra_weights(), 0.0, GuardAction::Restore);
+
+    /* only the word in the given position is checked */
+    let mut weights = vec![vec![9.0, 9.0], vec![0.3, 0.4]];
+    guard.check_weight((0, 1), &mut weights);
+    assert_eq!(weights, vec![vec![9.0, 0.2], vec![0.3, 0.4]]);
+    assert_eq!(guard.get_detected(), 1);
+}
+
+#[test]
+fn verify_not_guarded_component() {
+    /* a fault on v_mem is injected at each instant, but the guard does not scan the layer for it */
+    let mut layer = create_layer(Conf::new(vec![Components::VMem], Failure::StuckAt1(StuckAt1::new(1)), 0));
+    layer.enable_range_guard(0.0, GuardAction::Restore);
+    let mut weights = layer.get_weights();
+    weights[1][1] = 9.0;
+    layer.set_weights(weights);
+    run(&mut layer);
+
+    assert_eq!(layer.get_range_guard().unwrap().get_detected(), 0);
+    assert_eq!(layer.get_weights()[1][1], 9.0);
+}
@@ -0,0 +1,74 @@
+use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt1};
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::network::SNN;
+use spiking_neural_network::recording::{first_difference, SpikeRecord};
+use spiking_neural_network::snn::builder::SnnBuilder;
+use spiking_neural_network::spike_event::SpikeEvent;
+
+fn create_network(configuration: Conf) -> SNN<LifNeuron, Conf> {
+    SnnBuilder::<LifNeuron, Conf>::new(2)
+        .add_layer(vec![LifNeuron::new(0.5, 0.0, 0.0, 0.0, 1.0); 3],
+                   vec![vec![0.6, 0.0], vec![0.3, 0.3], vec![0.0, 0.2]],
+                   vec![vec![0.0; 3]; 3], configuration)
+        .add_layer(vec![LifNeuron::new(0.5, 0.0, 0.0, 0.0, 1.0); 2],
+                   vec![vec![0.6, 0.0, 0.0], vec![0.0, 0.3, 0.3]],
+                   vec![vec![0.0; 2]; 2], Conf::new(vec![], Failure::None, 0))
+        .build()
+}
+
+#[test]
+fn verify_spike_record() {
+    let mut record = SpikeRecord::new(3);
+    record.record(&SpikeEvent::new(2, vec![1, 0, 1]));
+    record.record(&SpikeEvent::new(5, vec![0, 0, 1]));
+
+    assert_eq!(record.get_spikes(), &[(2, 0), (2, 2), (5, 2)]);
+    assert_eq!(record.get_spike_counts(), vec![1, 0, 2]);
+    assert_eq!(record.get_firing_rates(10), vec![0.1, 0.0, 0.2]);
+
+    record.clear();
+    assert!(record.get_spikes().is_empty());
+}
+
+#[test]
+fn verify_hidden_layers_recording() {
+    let input = vec![vec![1, 1, 0, 1], vec![1, 0, 1, 1]];
+
+    let mut snn = create_network(Conf::new(vec![], Failure::None, 0));
+    assert!(snn.get_spike_records().iter().all(|record| record.get_spikes().is_empty()));
+
+    snn.enable_spike_recording();
+    let output = snn.process(&input);
+    let records = snn.get_spike_records();
+    assert_eq!(records.len(), 2);
+
+    /* without leak: neuron 0 fires at each spike of input 0, neuron 1 and neuron 2 each time they integrate more than 0.5 */
+    assert_eq!(records[0].get_spikes(), &[(0, 0), (0, 1), (1, 0), (2, 1), (3, 0), (3, 1), (3, 2)]);
+
+    /* the record of the last layer is the output of the network */
+    let mut expected = vec![vec![0u8; 4]; 2];
+    records[1].get_spikes().iter().for_each(|(t, neuron)| expected[*neuron][*t as usize] = 1);
+    assert_eq!(output, expected);
+
+    /* a new run starts a new record */
+    snn.process(&vec![vec![0], vec![0]]);
+    assert!(snn.get_spike_records()[0].get_spikes().is_empty());
+}
+
+#[test]
+fn verify_first_difference() {
+    let input = vec![vec![1, 1, 0, 1], vec![1, 0, 1, 1]];
+
+    let mut golden = create_network(Conf::new(vec![], Failure::None, 0));
+    golden.enable_spike_recording();
+    golden.process(&input);
+    let golden_records = golden.get_spike_records();
+    assert_eq!(first_difference(&golden_records, &golden_records), None);
+
+    /* the sign bit of the weight [1][0] makes the neuron 1 of the first layer silent */
+    let mut faulty = create_network(Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(64 * 3)), 0));
+    faulty.enable_spike_recording();
+    faulty.process(&input);
+
+    assert_eq!(first_difference(&golden_records, &faulty.get_spike_records()), Some((0, 0, 1)));
+}
@@ -0,0 +1,145 @@
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt1, TransientBitFlip};
+use spiking_neural_network::results::{FaultResult, read_results, ResultFormat, ResultStore, SampleOutcome};
+
+const SPEC: &str = "campaign seed=3 components=Weights,VMem neurons=400 bits=12 input=spikes.zip samples=3";
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("snn_test_results_{}_{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn create_results() -> Vec<FaultResult> {
+    let conf_weights = Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(3)), 7);
+    let mut weights = FaultResult::new(0, &conf_weights);
+    weights.row = Some(1);
+    weights.col = Some(5);
+    weights.outcomes = vec![SampleOutcome::Masked, SampleOutcome::Changed, SampleOutcome::Misclassified];
+    weights.injection_times = vec![Some(2), None, Some(0)];
+    weights.accuracy = Some(66.5);
+    weights.runtime = Duration::from_millis(1250);
+
+    let conf_v_mem = Conf::new(vec![Components::VMem], Failure::TransientBitFlip(TransientBitFlip::new(0)), 3);
+    let v_mem = FaultResult::new(1, &conf_v_mem);
+
+    vec![weights, v_mem]
+}
+
+#[test]
+fn verify_format_from_path() {
+    assert_eq!(ResultFormat::from_path("results.csv"), ResultFormat::Csv);
+    assert_eq!(ResultFormat::from_path("results.jsonl"), ResultFormat::Jsonl);
+    assert_eq!(ResultFormat::from_path("results"), ResultFormat::Csv);
+}
+
+#[test]
+fn verify_sample_outcome() {
+    let golden = vec![vec![1, 1, 0], vec![0, 0, 1]];
+    let changed = vec![vec![1, 0, 0], vec![0, 0, 1]];
+    let misclassified = vec![vec![0, 0, 0], vec![1, 0, 1]];
+    let assignments = [0, 1];
+
+    assert_eq!(SampleOutcome::from_outputs(&golden, &golden, Some(&assignments)), SampleOutcome::Masked);
+    assert_eq!(SampleOutcome::from_outputs(&golden, &changed, Some(&assignments)), SampleOutcome::Changed);
+    assert_eq!(SampleOutcome::from_outputs(&golden, &misclassified, Some(&assignments)), SampleOutcome::Misclassified);
+    assert_eq!(SampleOutcome::from_outputs(&golden, &misclassified, None), SampleOutcome::Changed);
+}
+
+#[test]
+fn verify_csv_store() {
+    let path = temp_path("store.csv");
+    let results = create_results();
+
+    let mut store = ResultStore::open(&path, ResultFormat::Csv, SPEC).unwrap();
+    results.iter().for_each(|result| store.append(result).unwrap());
+
+    let contents = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines, vec![
+        "# campaign seed=3 components=Weights,VMem neurons=400 bits=12 input=spikes.zip samples=3",
+        "layer,component,neuron,row,col,bit,failure,outcomes,injection_times,accuracy,runtime_ms",
+        "0,Weights,7,1,5,3,StuckAt1,masked;changed;misclassified,2;-;0,66.5,1250",
+        "1,VMem,3,,,0,TransientBitFlip,,,,0",
+    ]);
+    assert_eq!(read_results(&path, ResultFormat::Csv).unwrap(), results);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn verify_jsonl_store() {
+    let path = temp_path("store.jsonl");
+    let results = create_results();
+
+    let mut store = ResultStore::open(&path, ResultFormat::Jsonl, SPEC).unwrap();
+    results.iter().for_each(|result| store.append(result).unwrap());
+
+    let contents = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines[0], r#"{"spec":"campaign seed=3 components=Weights,VMem neurons=400 bits=12 input=spikes.zip samples=3"}"#);
+    assert_eq!(lines[1],
+               r#"{"layer":0,"component":"Weights","neuron":7,"row":1,"col":5,"bit":3,"failure":"StuckAt1","outcomes":["masked","changed","misclassified"],"injection_times":[2,null,0],"accuracy":66.5,"runtime_ms":1250}"#);
+    assert_eq!(read_results(&path, ResultFormat::Jsonl).unwrap(), results);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn verify_store_resume() {
+    let path = temp_path("resume.csv");
+    let results = create_results();
+
+    let mut store = ResultStore::open(&path, ResultFormat::Csv, SPEC).unwrap();
+    store.append(&results[0]).unwrap();
+    drop(store);
+
+    /* an interrupted run leaves an incomplete line */
+    OpenOptions::new().append(true).open(&path).unwrap().write_all(b"1,VMem,3,").unwrap();
+
+    let mut store = ResultStore::open(&path, ResultFormat::Csv, SPEC).unwrap();
+    assert_eq!(store.get_num_results(), 1);
+    assert!(store.contains(0, &Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(3)), 7)));
+    assert!(!store.contains(1, &Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(3)), 7)));
+    assert!(!store.contains(1, &Conf::new(vec![Components::VMem], Failure::TransientBitFlip(TransientBitFlip::new(0)), 3)));
+
+    store.append(&results[1]).unwrap();
+    assert!(store.contains(1, &Conf::new(vec![Components::VMem], Failure::TransientBitFlip(TransientBitFlip::new(0)), 3)));
+    assert_eq!(read_results(&path, ResultFormat::Csv).unwrap(), results);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn verify_store_other_run() {
+    let path = temp_path("other.csv");
+    let mut store = ResultStore::open(&path, ResultFormat::Csv, SPEC).unwrap();
+    store.append(&create_results()[0]).unwrap();
+    drop(store);
+
+    /* the results of another dataset are not resumed */
+    let error = ResultStore::open(&path, ResultFormat::Csv, &SPEC.replace("samples=3", "samples=5")).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert_eq!(ResultStore::open(&path, ResultFormat::Csv, SPEC).unwrap().get_num_results(), 1);
+
+    /* nor a file without specification */
+    fs::write(&path, "layer,component,neuron,row,col,bit,failure,outcomes,injection_times,accuracy,runtime_ms\n").unwrap();
+    assert_eq!(ResultStore::open(&path, ResultFormat::Csv, SPEC).err().unwrap().kind(), ErrorKind::InvalidInput);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn verify_invalid_results() {
+    let path = temp_path("invalid.csv");
+    fs::write(&path, "# campaign seed=3 components=Weights,VMem neurons=400 bits=12 input=spikes.zip samples=3\nlayer,component,neuron,row,col,bit,failure,outcomes,injection_times,accuracy,runtime_ms\n0,Spikes,7,,,3,StuckAt1,,,,0\n").unwrap();
+
+    assert!(ResultStore::open(&path, ResultFormat::Csv, SPEC).is_err());
+    assert!(read_results(&path, ResultFormat::Csv).is_err());
+
+    fs::remove_file(&path).unwrap();
+}
@@ -1,8 +1,10 @@
 use std::sync::{Arc, Mutex};
 use spiking_neural_network::lif_neuron::LifNeuron;
 use spiking_neural_network::layer::Layer;
-use spiking_neural_network::network::SNN;
-use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt0};
+use spiking_neural_network::network::{Executor, SNN};
+use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt0, StuckAt1};
+use spiking_neural_network::snn::builder::SnnBuilder;
+use spiking_neural_network::spike_vec::SpikeVec;
 
 fn create_layer() -> Layer<LifNeuron, Conf> {
     let n = LifNeuron::new(0.76, 0.33, 0.14, 0.4, 0.05);
@@ -83,4 +85,154 @@ fn verify_output_last_layer_dim() {
 fn verify_output_first_layer_dim() {
     let n = create_snn();
     assert_eq!(n.get_output_last_layer_dim(), 3);
-}
\ No newline at end of file
+}
+#[test]
+fn verify_fault_effective() {
+    /* StuckAt0 on the sign bit of a positive v_th and v_mem: only v_mem is rewritten at every instant */
+    let n = create_snn();
+    assert!(n.is_fault_effective(100));
+
+    let layer = Layer::new(vec![LifNeuron::new(0.76, 0.33, 0.14, 0.4, 0.05)], vec![vec![0.1, 0.2]], vec![vec![0.0]],
+                           Conf::new(vec![Components::VTh], Failure::StuckAt0(StuckAt0::new(0)), 0));
+    let n = SNN::new(vec![Arc::new(Mutex::new(layer))]);
+    assert!(!n.is_fault_effective(100));
+}
+
+#[test]
+fn verify_process_packed() {
+    let mut snn = SnnBuilder::<LifNeuron, Conf>::new(2)
+        .add_layer(vec![LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0); 2],
+                   vec![vec![1.0, 0.0], vec![0.0, 0.2]],
+                   vec![vec![0.0, -0.1], vec![-0.1, 0.0]],
+                   Conf::new(vec![], Failure::None, 0))
+        .build();
+    let spikes = vec![vec![1, 0, 1, 0], vec![1, 1, 1, 1]];
+
+    let expected = snn.process(&spikes);
+    let packed: Vec<SpikeVec> = spikes.iter().map(|neuron_spikes| SpikeVec::from(neuron_spikes.as_slice())).collect();
+    let output = snn.process_packed(&packed);
+
+    assert_eq!(output.iter().map(|neuron_spikes| neuron_spikes.to_vec()).collect::<Vec<Vec<u8>>>(), expected);
+}
+
+fn create_batch_network(configuration: Conf) -> SNN<LifNeuron, Conf> {
+    SnnBuilder::<LifNeuron, Conf>::new(3)
+        .add_layer(vec![LifNeuron::new(0.6, 0.0, 0.1, 1.0, 0.5); 3],
+                   vec![vec![0.5, 0.3, 0.0], vec![0.1, 0.4, 0.4], vec![0.0, 0.2, 0.7]],
+                   vec![vec![0.0, -0.1, -0.2], vec![-0.1, 0.0, -0.1], vec![-0.2, -0.1, 0.0]],
+                   configuration.clone())
+        .add_layer(vec![LifNeuron::new(0.3, 0.0, 0.0, 1.0, 0.5); 2],
+                   vec![vec![0.4, 0.1, 0.2], vec![0.0, 0.3, 0.5]],
+                   vec![vec![0.0, -0.05], vec![-0.05, 0.0]],
+                   Conf::new(vec![], Failure::None, 0))
+        .build()
+}
+
+fn create_batch_samples() -> Vec<Vec<Vec<u8>>> {
+    vec![
+        vec![vec![1, 0, 1, 1, 0], vec![0, 1, 1, 0, 0], vec![1, 1, 0, 0, 1]],
+        vec![vec![0, 0, 0], vec![0, 0, 0], vec![0, 0, 0]],
+        vec![vec![1, 1, 1, 1], vec![0, 0, 0, 0], vec![1, 0, 1, 0]],
+        vec![vec![0, 1], vec![1, 1], vec![1, 0]],
+    ]
+}
+
+#[test]
+fn verify_process_batch() {
+    let samples = create_batch_samples();
+
+    let mut snn = create_batch_network(Conf::new(vec![], Failure::None, 0));
+    let expected: Vec<Vec<Vec<u8>>> = samples.iter().map(|sample| snn.process(sample)).collect();
+
+    let mut snn = create_batch_network(Conf::new(vec![], Failure::None, 0));
+    assert_eq!(snn.process_batch(&samples), expected);
+    assert!(snn.process_batch(&[]).is_empty());
+}
+
+#[test]
+fn verify_process_batch_parallel() {
+    let samples = create_batch_samples();
+    let configuration = Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(0)), 0);
+
+    let expected = create_batch_network(configuration.clone()).process_batch(&samples);
+    assert_ne!(expected, create_batch_network(Conf::new(vec![], Failure::None, 0)).process_batch(&samples));
+    let snn = create_batch_network(configuration);
+    for num_networks in 1..=5 {
+        assert_eq!(snn.process_batch_parallel(&samples, num_networks), expected);
+    }
+}
+
+#[test]
+fn verify_lockstep_executor() {
+    let samples = create_batch_samples();
+    let configurations = vec![
+        Conf::new(vec![], Failure::None, 0),
+        Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(0)), 0),
+        Conf::new(vec![Components::VMem, Components::PrevSpikes], Failure::StuckAt1(StuckAt1::new(2)), 1),
+    ];
+
+    for configuration in configurations {
+        let expected = create_batch_network(configuration.clone()).process_batch(&samples);
+
+        let mut snn = create_batch_network(configuration.clone());
+        snn.set_executor(Executor::Lockstep);
+        assert_eq!(snn.process_batch(&samples), expected);
+
+        let mut snn = create_batch_network(configuration.clone());
+        let outputs: Vec<Vec<Vec<u8>>> = samples.iter().map(|sample| snn.process_with(sample, Executor::Lockstep)).collect();
+        assert_eq!(outputs, expected);
+    }
+}
+
+#[test]
+fn verify_executor_from_builder() {
+    let snn = SnnBuilder::<LifNeuron, Conf>::new(1)
+        .add_layer(vec![LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0)], vec![vec![1.0]], vec![vec![0.0]], Conf::new(vec![], Failure::None, 0))
+        .executor(Executor::Lockstep)
+        .build();
+    assert_eq!(snn.get_executor(), Executor::Lockstep);
+}
+
+#[test]
+fn verify_step() {
+    let samples = create_batch_samples();
+    let configuration = Conf::new(vec![Components::VMem], Failure::StuckAt1(StuckAt1::new(3)), 2);
+    let expected = create_batch_network(configuration.clone()).process_batch(&samples);
+
+    let mut snn = create_batch_network(configuration);
+    for (sample, expected_output) in samples.iter().zip(expected.iter()) {
+        snn.reset();
+        let duration = sample[0].len();
+        let mut output = vec![vec![0; duration]; expected_output.len()];
+        for t in 0..duration {
+            let input: Vec<u8> = sample.iter().map(|neuron_spikes| neuron_spikes[t]).collect();
+            for (neuron, spike) in snn.step(&input).into_iter().enumerate() {
+                output[neuron][t] = spike;
+            }
+        }
+        assert_eq!(snn.get_instant(), duration as u64);
+        assert_eq!(&output, expected_output);
+    }
+}
+
+#[test]
+fn verify_step_without_reset() {
+    /* the first step starts from the state of `process`, also for the previous spikes */
+    let sample = &create_batch_samples()[0];
+    let configuration = Conf::new(vec![Components::PrevSpikes], Failure::StuckAt1(StuckAt1::new(1)), 0);
+    let expected = create_batch_network(configuration.clone()).process(sample);
+
+    let mut snn = create_batch_network(configuration);
+    let steps: Vec<Vec<u8>> = (0..sample[0].len())
+        .map(|t| snn.step(&sample.iter().map(|neuron_spikes| neuron_spikes[t]).collect::<Vec<u8>>()))
+        .collect();
+    let output: Vec<Vec<u8>> = (0..expected.len()).map(|neuron| steps.iter().map(|spikes| spikes[neuron]).collect()).collect();
+    assert_eq!(output, expected);
+}
+
+#[test]
+#[should_panic(expected = "Error: number of input spikes is not coherent with the input layer dimension")]
+fn verify_step_wrong_dimension() {
+    let mut snn = create_batch_network(Conf::new(vec![], Failure::None, 0));
+    snn.step(&[1, 0]);
+}
@@ -0,0 +1,128 @@
+use rand::Rng;
+use spiking_neural_network::campaign::rng_from_seed;
+use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt1};
+use spiking_neural_network::layer::Layer;
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::snn::builder::SnnBuilder;
+use spiking_neural_network::spike_event::SpikeEvent;
+use spiking_neural_network::sparse::{SparseFormat, SparseMatrix};
+
+fn create_matrix() -> Vec<Vec<f64>> {
+    vec![
+        vec![0.0, 0.2, 0.0, 0.4],
+        vec![0.0, 0.0, 0.0, 0.0],
+        vec![0.5, 0.0, 0.7, 0.1],
+    ]
+}
+
+/* random matrix with about `density` non-zero weights */
+fn random_matrix(seed: u64, rows: usize, cols: usize, density: f64) -> Vec<Vec<f64>> {
+    let mut rng = rng_from_seed(seed, 0);
+    (0..rows).map(|_| (0..cols).map(|_| if rng.gen_bool(density) { rng.gen_range(0.0..1.0) } else { 0.0 }).collect()).collect()
+}
+
+#[test]
+fn verify_dense_round_trip() {
+    let matrix = create_matrix();
+    for format in [SparseFormat::Csr, SparseFormat::Csc] {
+        let sparse = SparseMatrix::from_dense(&matrix, format);
+        assert_eq!(sparse.get_format(), format);
+        assert_eq!((sparse.get_rows(), sparse.get_cols(), sparse.get_nnz()), (3, 4, 5));
+        assert_eq!(sparse.to_dense(), matrix);
+    }
+}
+
+#[test]
+fn verify_stored_values_order() {
+    let matrix = create_matrix();
+
+    let csr = SparseMatrix::from_dense(&matrix, SparseFormat::Csr);
+    assert_eq!(csr.get_values(), &vec![0.2, 0.4, 0.5, 0.7, 0.1]);
+    assert_eq!(csr.get_position(2), (2, 0));
+
+    let csc = SparseMatrix::from_dense(&matrix, SparseFormat::Csc);
+    assert_eq!(csc.get_values(), &vec![0.5, 0.2, 0.7, 0.4, 0.1]);
+    assert_eq!(csc.get_position(2), (2, 2));
+    assert_eq!(csc.get_position(4), (2, 3));
+}
+
+#[test]
+fn verify_spike_sums() {
+    let matrix = random_matrix(1, 20, 50, 0.2);
+    let spikes: Vec<u8> = (0..50).map(|j| (j % 3 == 0) as u8).collect();
+
+    let dense: Vec<f64> = matrix.iter()
+        .map(|row| row.iter().zip(spikes.iter()).filter(|(_, spike)| **spike != 0).fold(0.0, |sum, (weight, _)| sum + weight))
+        .collect();
+
+    /* same order of the additions: the sums are bit-identical */
+    let event = SpikeEvent::new(0, spikes);
+    assert_eq!(SparseMatrix::from_dense(&matrix, SparseFormat::Csr).spike_sums(&event), dense);
+    assert_eq!(SparseMatrix::from_dense(&matrix, SparseFormat::Csc).spike_sums(&event), dense);
+}
+
+#[test]
+fn verify_same_output_as_dense() {
+    let builder = SnnBuilder::<LifNeuron, Conf>::new(40)
+        .add_layer_with_same_neurons(LifNeuron::new(0.8, 0.0, 0.1, 1.0, 1.0), 30, random_matrix(2, 30, 40, 0.1),
+                                     vec![vec![0.0; 30]; 30], Conf::new(vec![], Failure::None, 0))
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.1, 1.0, 1.0), 10, random_matrix(3, 10, 30, 0.3),
+                                     vec![vec![-0.05; 10]; 10], Conf::new(vec![], Failure::None, 0));
+
+    let mut rng = rng_from_seed(4, 0);
+    let input: Vec<Vec<u8>> = (0..40).map(|_| (0..50).map(|_| rng.gen_bool(0.3) as u8).collect()).collect();
+
+    let expected = builder.clone().build().process(&input);
+    for format in [SparseFormat::Csr, SparseFormat::Csc] {
+        let mut snn = builder.clone().sparse_weights(format).build();
+        assert_eq!(snn.process(&input), expected);
+        assert_eq!(snn.get_input_layer_one_dim(), 40);
+    }
+}
+
+#[test]
+fn verify_fault_on_stored_values() {
+    /* bit 1 of the third stored value: the exponent MSB, 0.5 becomes huge */
+    let failure = Failure::StuckAt1(StuckAt1::new(64 * 2 + 1));
+    let mut snn = SnnBuilder::<LifNeuron, Conf>::new(4)
+        .add_layer(vec![LifNeuron::new(0.9, 0.0, 0.0, 1.0, 1.0); 3], create_matrix(), vec![vec![0.0; 3]; 3],
+                   Conf::new(vec![Components::Weights], failure, 0))
+        .sparse_weights(SparseFormat::Csr)
+        .build();
+    snn.process(&vec![vec![1]; 4]);
+
+    let weights = snn.get_layers()[0].get_weights();
+    let mut expected = create_matrix();
+    expected[2][0] = f64::from_bits(0.5f64.to_bits() | (1 << 62));
+    assert_eq!(weights, expected);
+}
+
+#[test]
+fn verify_fault_position_wraps() {
+    /* the positions beyond the stored values wrap around them, zero weights are never hit */
+    let mut layer = Layer::new(vec![LifNeuron::new(0.9, 0.0, 0.0, 1.0, 1.0); 3], create_matrix(), vec![vec![0.0; 3]; 3],
+                               Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(64 * 7 + 1)), 0));
+    layer.enable_sparse_weights(SparseFormat::Csc);
+    assert!(layer.is_fault_effective(100));
+
+    let mut empty = Layer::new(vec![LifNeuron::new(0.9, 0.0, 0.0, 1.0, 1.0); 3], vec![vec![0.0; 4]; 3], vec![vec![0.0; 3]; 3],
+                               Conf::new(vec![Components::Weights], Failure::StuckAt1(StuckAt1::new(1)), 0));
+    empty.enable_sparse_weights(SparseFormat::Csr);
+    assert!(!empty.is_fault_effective(100));
+}
+
+#[test]
+fn verify_set_weights_keeps_format() {
+    let mut layer = Layer::new(vec![LifNeuron::new(0.9, 0.0, 0.0, 1.0, 1.0); 3], create_matrix(), vec![vec![0.0; 3]; 3],
+                               Conf::new(vec![], Failure::None, 0));
+    layer.enable_sparse_weights(SparseFormat::Csc);
+
+    let mut matrix = create_matrix();
+    matrix[1][1] = 0.3;
+    layer.set_weights(matrix.clone());
+
+    let sparse = layer.get_sparse_weights().unwrap();
+    assert_eq!((sparse.get_format(), sparse.get_nnz()), (SparseFormat::Csc, 6));
+    assert_eq!(layer.get_weights(), matrix);
+    assert_eq!(layer.get_input_dimension(), 4);
+}
@@ -0,0 +1,11 @@
+use spiking_neural_network::spike_event::SpikeEvent;
+
+#[test]
+fn verify_spike_event() {
+    let event = SpikeEvent::new(7, vec![1, 0, 0, 1]);
+    assert_eq!(event.get_ts(), 7);
+    assert_eq!(event.get_spikes().to_vec(), vec![1, 0, 0, 1]);
+    assert_eq!(event.get_active().collect::<Vec<usize>>(), vec![0, 3]);
+    assert!(!event.is_empty());
+    assert!(SpikeEvent::new(8, vec![0, 0, 0, 0]).is_empty());
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use std::io::{Cursor, ErrorKind};
+use std::path::PathBuf;
+use spiking_neural_network::spike_file::{convert_spike_file, SpikeEncoding, SpikeFileReader, SpikeFileWriter};
+use spiking_neural_network::spike_reader::SpikeReader;
+use spiking_neural_network::spike_vec::SpikeVec;
+
+/* 2 samples of 3 instants with 4 input neurons */
+const SPIKES: &str = "1000\n0100\n0011\n1111\n0000\n1010\n";
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("snn_test_spike_file_{}_{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn expected_samples() -> Vec<Vec<Vec<u8>>> {
+    vec![
+        vec![vec![1, 0, 0], vec![0, 1, 0], vec![0, 0, 1], vec![0, 0, 1]],
+        vec![vec![1, 0, 1], vec![1, 0, 0], vec![1, 0, 1], vec![1, 0, 0]],
+    ]
+}
+
+fn write_samples(encoding: SpikeEncoding, samples: &[Vec<Vec<u8>>]) -> Vec<u8> {
+    let mut writer = SpikeFileWriter::new(Cursor::new(Vec::new()), encoding, 4, 3).unwrap();
+    for sample in samples {
+        writer.write_sample(sample).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[test]
+fn verify_round_trip() {
+    for encoding in [SpikeEncoding::Dense, SpikeEncoding::Events] {
+        let bytes = write_samples(encoding, &expected_samples());
+        let reader = SpikeFileReader::new(Cursor::new(bytes)).unwrap();
+
+        let header = reader.get_header();
+        assert_eq!(header.encoding, encoding);
+        assert_eq!((header.num_inputs, header.num_instants, header.num_samples), (4, 3, 2));
+        assert_eq!(reader.collect::<std::io::Result<Vec<_>>>().unwrap(), expected_samples());
+    }
+}
+
+#[test]
+fn verify_sizes() {
+    /* header, then 4 neurons of 1 word for each sample */
+    assert_eq!(write_samples(SpikeEncoding::Dense, &expected_samples()).len(), 24 + 2 * 4 * 8);
+    /* header, then the count and 8 bytes for each of the 4 and 6 spikes */
+    assert_eq!(write_samples(SpikeEncoding::Events, &expected_samples()).len(), 24 + 4 + 4 * 8 + 4 + 6 * 8);
+}
+
+#[test]
+fn verify_packed_samples() {
+    let bytes = write_samples(SpikeEncoding::Events, &expected_samples());
+    let mut reader = SpikeFileReader::new(Cursor::new(bytes)).unwrap();
+
+    let packed = reader.next_packed_sample().unwrap().unwrap();
+    let expected: Vec<SpikeVec> = expected_samples()[0].iter().map(|spikes| SpikeVec::from(spikes.as_slice())).collect();
+    assert_eq!(packed, expected);
+}
+
+#[test]
+fn verify_invalid_sample() {
+    let mut writer = SpikeFileWriter::new(Cursor::new(Vec::new()), SpikeEncoding::Dense, 4, 3).unwrap();
+    assert_eq!(writer.write_sample(&[vec![0, 1, 0]]).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(writer.write_sample(&vec![vec![0, 1]; 4]).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(writer.get_header().num_samples, 0);
+}
+
+#[test]
+fn verify_invalid_file() {
+    let mut bytes = write_samples(SpikeEncoding::Dense, &expected_samples());
+    bytes[0] = b'X';
+    assert_eq!(SpikeFileReader::new(Cursor::new(bytes)).err().unwrap().kind(), ErrorKind::InvalidData);
+
+    /* truncated in the second sample */
+    let mut bytes = write_samples(SpikeEncoding::Events, &expected_samples());
+    bytes.truncate(bytes.len() - 4);
+    let mut reader = SpikeFileReader::new(Cursor::new(bytes)).unwrap();
+    assert!(reader.next_sample().is_ok());
+    assert_eq!(reader.next_sample().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn verify_conversion() {
+    let text = temp_path("spikes.txt");
+    let binary = temp_path("spikes.spk");
+    fs::write(&text, SPIKES).unwrap();
+
+    assert_eq!(convert_spike_file(&text, &binary, 4, 3, SpikeEncoding::Events).unwrap(), 2);
+    let samples = SpikeReader::open(&binary, 4, 3).unwrap().collect::<std::io::Result<Vec<_>>>().unwrap();
+    assert_eq!(samples, expected_samples());
+
+    /* the dimensions must be the ones of the file */
+    assert_eq!(SpikeReader::open(&binary, 4, 5).err().unwrap().kind(), ErrorKind::InvalidData);
+
+    fs::remove_file(&text).unwrap();
+    fs::remove_file(&binary).unwrap();
+}
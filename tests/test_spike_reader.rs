@@ -0,0 +1,97 @@
+use std::fs;
+use std::fs::File;
+use std::io::{Cursor, ErrorKind, Write};
+use std::path::PathBuf;
+use zip::write::{FileOptions, ZipWriter};
+use spiking_neural_network::spike_reader::{SpikeDecoder, SpikeReader};
+
+/* 2 samples of 3 instants with 4 input neurons */
+const SPIKES: &str = "1000\n0100\n0011\n1111\n0000\n1010\n";
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("snn_test_spike_reader_{}_{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn expected_samples() -> Vec<Vec<Vec<u8>>> {
+    vec![
+        vec![vec![1, 0, 0], vec![0, 1, 0], vec![0, 0, 1], vec![0, 0, 1]],
+        vec![vec![1, 0, 1], vec![1, 0, 0], vec![1, 0, 1], vec![1, 0, 0]],
+    ]
+}
+
+#[test]
+fn verify_decoder() {
+    let mut decoder = SpikeDecoder::new(Cursor::new(SPIKES), 4, 3);
+
+    assert_eq!(decoder.next_sample().unwrap(), Some(expected_samples()[0].clone()));
+    assert_eq!(decoder.next_sample().unwrap(), Some(expected_samples()[1].clone()));
+    assert_eq!(decoder.next_sample().unwrap(), None);
+}
+
+#[test]
+fn verify_decoder_crlf() {
+    let decoder = SpikeDecoder::new(Cursor::new(SPIKES.replace('\n', "\r\n")), 4, 3);
+    assert_eq!(decoder.collect::<std::io::Result<Vec<_>>>().unwrap(), expected_samples());
+}
+
+#[test]
+fn verify_decoder_errors() {
+    let mut decoder = SpikeDecoder::new(Cursor::new("1000\n0100\n0011\n1111\n"), 4, 3);
+    assert!(decoder.next_sample().is_ok());
+    assert_eq!(decoder.next_sample().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+
+    let mut decoder = SpikeDecoder::new(Cursor::new("1000\n010\n0011\n"), 4, 3);
+    assert_eq!(decoder.next_sample().unwrap_err().kind(), ErrorKind::InvalidData);
+
+    let mut decoder = SpikeDecoder::new(Cursor::new("1000\n0120\n0011\n"), 4, 3);
+    assert_eq!(decoder.next_sample().unwrap_err().kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn verify_reader_text() {
+    let path = temp_path("spikes.txt");
+    fs::write(&path, SPIKES).unwrap();
+
+    let samples = SpikeReader::open(&path, 4, 3).unwrap().collect::<std::io::Result<Vec<_>>>().unwrap();
+    assert_eq!(samples, expected_samples());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn verify_reader_zip() {
+    let path = temp_path("spikes.zip");
+    let mut zip = ZipWriter::new(File::create(&path).unwrap());
+    zip.start_file("inputSpikes.txt", FileOptions::default()).unwrap();
+    zip.write_all(SPIKES.as_bytes()).unwrap();
+    zip.finish().unwrap();
+
+    let samples = SpikeReader::open(&path, 4, 3).unwrap().collect::<std::io::Result<Vec<_>>>().unwrap();
+    assert_eq!(samples, expected_samples());
+
+    /* only the samples needed are decoded */
+    let first = SpikeReader::open(&path, 4, 3).unwrap().next().unwrap().unwrap();
+    assert_eq!(first, expected_samples()[0]);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn verify_reader_stops_at_error() {
+    let path = temp_path("invalid.txt");
+    fs::write(&path, "1000\n0100\n0011\n1x11\n0000\n1010\n1000\n0100\n0011\n").unwrap();
+
+    let mut reader = SpikeReader::open(&path, 4, 3).unwrap();
+    assert!(reader.next().unwrap().is_ok());
+    assert_eq!(reader.next().unwrap().unwrap_err().kind(), ErrorKind::InvalidData);
+    assert!(reader.next().is_none());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn verify_reader_missing_file() {
+    assert!(SpikeReader::open(temp_path("missing.zip"), 4, 3).is_err());
+}
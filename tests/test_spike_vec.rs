@@ -0,0 +1,47 @@
+use spiking_neural_network::spike_vec::SpikeVec;
+
+#[test]
+fn verify_new() {
+    let vec = SpikeVec::new(130);
+    assert_eq!(vec.len(), 130);
+    assert_eq!(vec.get_words().len(), 3);
+    assert!(!vec.any());
+    assert!(SpikeVec::default().is_empty());
+}
+
+#[test]
+fn verify_set_get() {
+    let mut vec = SpikeVec::new(100);
+    vec.set(0, 1);
+    vec.set(63, 1);
+    vec.set(64, 1);
+    vec.set(99, 1);
+    vec.set(63, 0);
+
+    assert_eq!((vec.get(0), vec.get(63), vec.get(64), vec.get(99)), (1, 0, 1, 1));
+    assert_eq!(vec.get(100), 0);
+    assert_eq!(vec.count_ones(), 3);
+    assert_eq!(vec.iter_ones().collect::<Vec<usize>>(), vec![0, 64, 99]);
+}
+
+#[test]
+#[should_panic]
+fn verify_set_out_of_range() {
+    SpikeVec::new(10).set(10, 1);
+}
+
+#[test]
+fn verify_push_and_conversion() {
+    let spikes: Vec<u8> = (0..70).map(|i| (i % 7 == 0) as u8).collect();
+
+    let mut pushed = SpikeVec::default();
+    spikes.iter().for_each(|spike| pushed.push(*spike));
+
+    let converted = SpikeVec::from(spikes.clone());
+    assert_eq!(pushed, converted);
+    assert_eq!(converted.to_vec(), spikes);
+    assert_eq!(converted.iter().collect::<Vec<u8>>(), spikes);
+
+    pushed.clear();
+    assert!(pushed.is_empty() && !pushed.any());
+}
@@ -0,0 +1,54 @@
+use spiking_neural_network::recording::SpikeRecord;
+use spiking_neural_network::svg::{firing_rate_svg, raster_svg};
+
+fn count_rects(svg: &str, color: &str) -> usize {
+    svg.matches(&format!("fill=\"{}\"/>", color)).count()
+}
+
+#[test]
+fn verify_spike_record_from_spikes() {
+    let record = SpikeRecord::from_spikes(&[vec![0, 1, 1], vec![1, 0, 1]]);
+    assert_eq!(record.get_num_neurons(), 2);
+    assert_eq!(record.get_spikes(), &[(0, 1), (1, 0), (2, 0), (2, 1)]);
+}
+
+#[test]
+fn verify_raster() {
+    let record = SpikeRecord::from_spikes(&[vec![0, 1, 1, 0], vec![1, 0, 1, 0]]);
+    let svg = raster_svg("Layer <0> & faults", &record, 4, None);
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert!(svg.contains("Layer &lt;0&gt; &amp; faults"));
+    assert_eq!(count_rects(&svg, "#000000"), 4);
+    assert!(!svg.contains("golden only"));
+}
+
+#[test]
+fn verify_raster_overlay() {
+    let golden = SpikeRecord::from_spikes(&[vec![0, 1, 1, 0], vec![1, 0, 1, 0]]);
+    let faulty = SpikeRecord::from_spikes(&[vec![0, 1, 0, 0], vec![1, 0, 1, 1]]);
+    let svg = raster_svg("overlay", &faulty, 4, Some(&golden));
+
+    /* 3 common spikes, 1 missing and 1 added by the fault (plus one rect for each legend entry) */
+    assert_eq!(count_rects(&svg, "#000000"), 3 + 1);
+    assert_eq!(count_rects(&svg, "#1f77b4"), 1 + 1);
+    assert_eq!(count_rects(&svg, "#d62728"), 1 + 1);
+}
+
+#[test]
+fn verify_firing_rates() {
+    let record = SpikeRecord::from_spikes(&[vec![1, 1, 1, 1], vec![1, 0, 0, 0], vec![0; 4]]);
+    let svg = firing_rate_svg("rates", &record, 4, None);
+
+    /* the highest rate fills the plot, the others are proportional */
+    assert!(svg.contains(r##"height="400.00" fill="#000000"/>"##));
+    assert!(svg.contains(r##"height="100.00" fill="#000000"/>"##));
+    assert!(svg.contains(r##"height="0.00" fill="#000000"/>"##));
+    assert!(svg.contains(">1.000</text>"));
+
+    let golden = SpikeRecord::from_spikes(&[vec![1, 1, 0, 0], vec![0; 4], vec![0; 4]]);
+    let overlay = firing_rate_svg("rates", &record, 4, Some(&golden));
+    assert_eq!(count_rects(&overlay, "#1f77b4"), 3 + 1);
+    assert_eq!(count_rects(&overlay, "#d62728"), 3 + 1);
+}
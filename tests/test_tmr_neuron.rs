@@ -0,0 +1,66 @@
+use spiking_neural_network::failure::{Components, Conf, Failure, StuckAt1};
+use spiking_neural_network::layer::Layer;
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::neuron::Neuron;
+use spiking_neural_network::tmr_neuron::TmrNeuron;
+use std::sync::mpsc::channel;
+use spiking_neural_network::spike_event::SpikeEvent;
+
+#[test]
+fn verify_same_output_without_faults() {
+    let mut n = LifNeuron::new(0.5, 0.2, 0.3, 0.4, 0.05);
+    let mut tmr = TmrNeuron::new(n.clone(), false);
+
+    for (t, sum) in [0.1, 0.5, 0.0, 0.9].into_iter().enumerate() {
+        assert_eq!(tmr.calculate_v_mem(t as u64, sum), n.calculate_v_mem(t as u64, sum));
+    }
+    assert_eq!(tmr.get_disagreements(), 0);
+}
+
+#[test]
+fn verify_fault_lands_in_one_replica() {
+    let mut tmr = TmrNeuron::new(LifNeuron::new(0.5, 0.2, 0.3, 0.4, 0.05), false);
+    tmr.set_v_th(-1.0);
+
+    let replicas = tmr.get_replicas();
+    assert_eq!(replicas[0].get_v_th(), -1.0);
+    assert_eq!(replicas[1].get_v_th(), 0.5);
+    assert_eq!(replicas[2].get_v_th(), 0.5);
+
+    /* the faulty replica always fires, the vote masks it */
+    assert_eq!(tmr.calculate_v_mem(1, 0.0), 0);
+    assert_eq!(tmr.get_disagreements(), 1);
+}
+
+#[test]
+fn verify_vote_state() {
+    let mut tmr = TmrNeuron::new(LifNeuron::new(0.5, 0.2, 0.3, 0.4, 0.05), true);
+    tmr.set_v_mem(100.0);
+
+    assert_eq!(tmr.calculate_v_mem(1, 0.1), 0);
+    for replica in tmr.get_replicas() {
+        assert_eq!(replica.get_v_mem(), tmr.get_replicas()[1].get_v_mem());
+    }
+
+    tmr.init();
+    assert_eq!(tmr.get_disagreements(), 0);
+    assert_eq!(tmr.get_v_mem(), 0.2);
+}
+
+#[test]
+fn verify_layer_with_tmr_neurons() {
+    let neurons = vec![TmrNeuron::new(LifNeuron::new(0.5, 0.2, 0.3, 0.4, 0.05), false); 2];
+    let configuration = Conf::new(vec![Components::VTh], Failure::StuckAt1(StuckAt1::new(0)), 0);
+    let mut layer = Layer::new(neurons, vec![vec![0.1], vec![0.1]], vec![vec![0.0; 2]; 2], configuration);
+
+    let (input_tx, input_rc) = channel();
+    let (output_tx, output_rc) = channel();
+    input_tx.send(SpikeEvent::new(0, vec![1])).unwrap();
+    drop(input_tx);
+    layer.process(input_rc, output_tx);
+
+    /* the stuck-at-1 on the sign bit makes the first replica of neuron 0 fire, masked by the others */
+    assert!(output_rc.recv().is_err());
+    assert_eq!(layer.get_neurons()[0].get_replicas()[0].get_v_th(), -0.5);
+    assert_eq!(layer.get_neurons()[0].get_disagreements(), 1);
+}
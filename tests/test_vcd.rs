@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+use spiking_neural_network::failure::{Conf, Failure};
+use spiking_neural_network::lif_neuron::LifNeuron;
+use spiking_neural_network::probe::{Probe, ProbeSample};
+use spiking_neural_network::recording::SpikeRecord;
+use spiking_neural_network::snn::builder::SnnBuilder;
+use spiking_neural_network::spike_event::SpikeEvent;
+use spiking_neural_network::vcd::{write_vcd, VcdRun};
+
+fn to_string(runs: &[VcdRun]) -> String {
+    let mut buffer = Vec::new();
+    write_vcd(&mut buffer, runs).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+#[test]
+fn verify_vcd_file() {
+    let mut record = SpikeRecord::new(2);
+    record.record(&SpikeEvent::new(1, vec![1, 0]));
+    record.record(&SpikeEvent::new(2, vec![1, 1]));
+
+    let mut probe = Probe::new(0, 1);
+    for (instant, v_mem) in [(1, 0.25), (2, 0.0)] {
+        probe.record(ProbeSample { instant, v_mem, ts: instant, v_th: 0.5, extra_sum: 0.25, intra_sum: 0.0, spike: 0 });
+    }
+
+    let expected = "\
+$version spiking_neural_network $end
+$timescale 1 ns $end
+$scope module golden_run $end
+$scope module layer_0 $end
+$var wire 1 ! n0 $end
+$var wire 1 \" n1 $end
+$var real 64 # v_mem_n1 $end
+$upscope $end
+$upscope $end
+$enddefinitions $end
+#0
+$dumpvars
+0!
+0\"
+$end
+#1
+1!
+r2.5e-1 #
+#2
+1!
+1\"
+r0e0 #
+#3
+0!
+0\"
+";
+    /* the spaces in the name of the run are replaced */
+    assert_eq!(to_string(&[VcdRun::new("golden run", vec![record], vec![probe])]), expected);
+}
+
+#[test]
+fn verify_golden_and_faulty_scopes() {
+    let builder = SnnBuilder::<LifNeuron, Conf>::new(2)
+        .add_layer(vec![LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0); 120], vec![vec![0.6, 0.0]; 120], vec![vec![0.0; 120]; 120],
+                   Conf::new(vec![], Failure::None, 0))
+        .add_layer(vec![LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0); 2], vec![vec![0.01; 120]; 2], vec![vec![0.0; 2]; 2],
+                   Conf::new(vec![], Failure::None, 0));
+    let input = vec![vec![1, 0, 1], vec![0, 1, 1]];
+
+    let runs: Vec<VcdRun> = ["golden", "faulty"].iter().map(|name| {
+        let mut snn = builder.clone().build();
+        snn.enable_spike_recording();
+        snn.add_probe(1, 0);
+        snn.process(&input);
+        VcdRun::new(name, snn.get_spike_records(), snn.get_probes())
+    }).collect();
+    let vcd = to_string(&runs);
+
+    assert_eq!(vcd.matches("$scope module layer_").count(), 4);
+    assert!(vcd.contains("$scope module golden $end") && vcd.contains("$scope module faulty $end"));
+
+    /* one identifier for each of the 2 * (120 + 2 + 1) signals */
+    let ids: HashSet<&str> = vcd.lines().filter(|line| line.starts_with("$var")).map(|line| line.split(' ').nth(3).unwrap()).collect();
+    assert_eq!(ids.len(), 246);
+}